@@ -0,0 +1,34 @@
+(function () {
+    var input = document.getElementById("search");
+    var results = document.getElementById("search-results");
+    if (!input || !results || !window.AMS_SEARCH_INDEX) {
+        return;
+    }
+
+    input.addEventListener("input", function () {
+        var query = input.value.trim().toLowerCase();
+        results.innerHTML = "";
+        if (query.length === 0) {
+            return;
+        }
+
+        window.AMS_SEARCH_INDEX
+            .filter(function (entry) {
+                return entry.title.toLowerCase().indexOf(query) !== -1
+                    || entry.text.toLowerCase().indexOf(query) !== -1;
+            })
+            .slice(0, 20)
+            .forEach(function (entry) {
+                var item = document.createElement("li");
+                var link = document.createElement("a");
+                link.href = entry.url;
+                link.textContent = entry.title;
+                var kind = document.createElement("span");
+                kind.className = "kind";
+                kind.textContent = " " + entry.kind;
+                item.appendChild(link);
+                item.appendChild(kind);
+                results.appendChild(item);
+            });
+    });
+})();
@@ -0,0 +1,515 @@
+use std::path::PathBuf;
+
+use crate::codegen::enums::{literal, render_enum};
+use crate::codegen::objects::render_object;
+use crate::entity::{
+    CollectionKind, CollectionTypeDefinition, Deprecation, Environment, PrimitiveType,
+    ProjectIdentifier, TypeDefinition, TypeDefinitionIdentifier,
+};
+use crate::export::naming::snake_case;
+use crate::export::{ExportError, ExportedFile};
+use crate::source::entity::{EPath, TargetLanguage};
+
+/// Generates native declarations for the types of the target project that can't be expressed
+/// by a native binding, enums and objects, one file per type.
+///
+/// Kotlin and Java declarations are placed in a package made of the project group, name and
+/// module path, e.g. `tuna_bank.client_app.common`.
+#[derive(Debug)]
+pub struct CodeGenerator<'env> {
+    env: &'env Environment,
+    language: TargetLanguage,
+}
+
+impl<'env> CodeGenerator<'env> {
+    pub fn of(env: &'env Environment, language: TargetLanguage) -> Self {
+        CodeGenerator { env, language }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let mut buf = Vec::new();
+        for definition in self.env.type_definitions() {
+            if definition.identifier().project() != self.env.identifier() {
+                continue;
+            }
+            let content = match definition {
+                TypeDefinition::Enum(enum_definition) => render_enum(self, enum_definition),
+                TypeDefinition::Object(object_definition) => render_object(self, object_definition),
+                TypeDefinition::NativeBinding(_)
+                | TypeDefinition::Alias(_)
+                | TypeDefinition::Collection(_) => continue,
+            };
+            buf.push(ExportedFile::new(
+                self.file_path(definition.identifier()),
+                content,
+            ));
+        }
+        Ok(buf)
+    }
+
+    pub fn language(&self) -> &TargetLanguage {
+        &self.language
+    }
+
+    pub(crate) fn env(&self) -> &'env Environment {
+        self.env
+    }
+
+    /// Name of the type in the target language as used by the declaration of `context`: the
+    /// generated declaration for enums and objects, qualified with its package when it is in
+    /// another one, the native collection for lists and maps, the native binding otherwise.
+    pub fn type_name(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+        context: &TypeDefinitionIdentifier,
+    ) -> String {
+        if let Some(collection) = self.env.collection_definition(identifier) {
+            return self.collection_type_name(collection, context);
+        }
+        if let Some(generated) = self.generated_declaration(identifier) {
+            let package = self.package(generated);
+            return match self.language {
+                TargetLanguage::Kotlin | TargetLanguage::Java
+                    if package != self.package(context) =>
+                {
+                    format!("{}.{}", package, generated.name())
+                }
+                _ => generated.name().to_string(),
+            };
+        }
+        self.env
+            .native_bindings(identifier)
+            .and_then(|bindings| bindings.get(&self.language))
+            .cloned()
+            .unwrap_or_else(|| self.primitive_type_name(self.env.primitive_type(identifier)))
+    }
+
+    /// Type of the values of the enum at `identifier`, its underlying type or a string.
+    pub(crate) fn enum_value_type_name(&self, identifier: &TypeDefinitionIdentifier) -> String {
+        let underlying = self
+            .env
+            .enum_definition(identifier)
+            .and_then(|definition| definition.underlying().cloned())
+            .unwrap_or_else(|| {
+                TypeDefinitionIdentifier::new(
+                    ProjectIdentifier::new("ams", "std"),
+                    EPath::empty().extended("String"),
+                )
+            });
+        self.type_name(&underlying, identifier)
+    }
+
+    /// Identifier of the enum or object generated for the type, following aliases.
+    pub(crate) fn generated_declaration(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+    ) -> Option<&'env TypeDefinitionIdentifier> {
+        match self.env.terminal_type_definition(identifier)? {
+            definition @ (TypeDefinition::Enum(_) | TypeDefinition::Object(_)) => {
+                Some(definition.identifier())
+            }
+            TypeDefinition::NativeBinding(_)
+            | TypeDefinition::Alias(_)
+            | TypeDefinition::Collection(_) => None,
+        }
+    }
+
+    /// Generated declarations referenced by the type, unwrapping collections down to their
+    /// elements.
+    fn referenced_declarations(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+    ) -> Vec<&'env TypeDefinitionIdentifier> {
+        if let Some(collection) = self.env.collection_definition(identifier) {
+            return self.referenced_declarations(collection.element());
+        }
+        self.generated_declaration(identifier).into_iter().collect()
+    }
+
+    /// Native collection of the target language, e.g. `kotlin.collections.List<kotlin.Int>`.
+    fn collection_type_name(
+        &self,
+        collection: &CollectionTypeDefinition,
+        context: &TypeDefinitionIdentifier,
+    ) -> String {
+        let element = self.type_name(collection.element(), context);
+        match (&self.language, collection.kind()) {
+            (TargetLanguage::Kotlin, CollectionKind::List) => {
+                format!("kotlin.collections.List<{}>", element)
+            }
+            (TargetLanguage::Kotlin, CollectionKind::Map) => {
+                format!("kotlin.collections.Map<kotlin.String, {}>", element)
+            }
+            (TargetLanguage::Java, CollectionKind::List) => format!("java.util.List<{}>", element),
+            (TargetLanguage::Java, CollectionKind::Map) => {
+                format!("java.util.Map<java.lang.String, {}>", element)
+            }
+            (TargetLanguage::Swift, CollectionKind::List) => format!("[{}]", element),
+            (TargetLanguage::Swift, CollectionKind::Map) => format!("[String: {}]", element),
+            (TargetLanguage::Dart, CollectionKind::List) => format!("List<{}>", element),
+            (TargetLanguage::Dart, CollectionKind::Map) => format!("Map<String, {}>", element),
+        }
+    }
+
+    /// Dart imports of the generated declarations referenced by the fields of `identifier`,
+    /// relative to its own file.
+    pub(crate) fn dart_imports(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+        field_types: &[&TypeDefinitionIdentifier],
+    ) -> Vec<String> {
+        let own_path = self.file_path(identifier);
+        let depth = own_path.components().count() - 1;
+        let mut imports: Vec<String> = field_types
+            .iter()
+            .flat_map(|field_type| self.referenced_declarations(field_type))
+            .filter(|generated| *generated != identifier)
+            .map(|generated| {
+                let path = self.file_path(generated);
+                let segments: Vec<String> = path
+                    .components()
+                    .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                    .collect();
+                format!("{}{}", "../".repeat(depth), segments.join("/"))
+            })
+            .collect();
+        imports.sort();
+        imports.dedup();
+        imports
+    }
+
+    pub(crate) fn primitive_type(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+    ) -> Option<PrimitiveType> {
+        self.env.primitive_type(identifier)
+    }
+
+    fn primitive_type_name(&self, primitive_type: Option<PrimitiveType>) -> String {
+        let name = match (&self.language, primitive_type) {
+            (TargetLanguage::Kotlin, Some(PrimitiveType::Integer)) => "Int",
+            (TargetLanguage::Kotlin, Some(PrimitiveType::Long)) => "Long",
+            (TargetLanguage::Kotlin, Some(PrimitiveType::Float)) => "Double",
+            (TargetLanguage::Kotlin, Some(PrimitiveType::Boolean)) => "Boolean",
+            (TargetLanguage::Kotlin, _) => "String",
+            (TargetLanguage::Java, Some(PrimitiveType::Integer)) => "Integer",
+            (TargetLanguage::Java, Some(PrimitiveType::Long)) => "Long",
+            (TargetLanguage::Java, Some(PrimitiveType::Float)) => "Double",
+            (TargetLanguage::Java, Some(PrimitiveType::Boolean)) => "Boolean",
+            (TargetLanguage::Java, _) => "String",
+            (TargetLanguage::Swift, Some(PrimitiveType::Integer)) => "Int",
+            (TargetLanguage::Swift, Some(PrimitiveType::Long)) => "Int64",
+            (TargetLanguage::Swift, Some(PrimitiveType::Float)) => "Double",
+            (TargetLanguage::Swift, Some(PrimitiveType::Boolean)) => "Bool",
+            (TargetLanguage::Swift, _) => "String",
+            (TargetLanguage::Dart, Some(PrimitiveType::Integer | PrimitiveType::Long)) => "int",
+            (TargetLanguage::Dart, Some(PrimitiveType::Float)) => "double",
+            (TargetLanguage::Dart, Some(PrimitiveType::Boolean)) => "bool",
+            (TargetLanguage::Dart, _) => "String",
+        };
+        name.to_string()
+    }
+
+    /// Package of Kotlin and Java declarations, e.g. `tuna_bank.client_app.common`.
+    pub(crate) fn package(&self, identifier: &TypeDefinitionIdentifier) -> String {
+        self.package_segments(identifier).join(".")
+    }
+
+    fn package_segments(&self, identifier: &TypeDefinitionIdentifier) -> Vec<String> {
+        let project = identifier.project();
+        let module = identifier
+            .path()
+            .segments
+            .split_last()
+            .map(|(_, module)| module);
+        [project.group(), project.name()]
+            .into_iter()
+            .chain(module.unwrap_or_default().iter().map(String::as_str))
+            .map(snake_case)
+            .collect()
+    }
+
+    fn file_path(&self, identifier: &TypeDefinitionIdentifier) -> PathBuf {
+        let name = identifier.name();
+        match self.language {
+            TargetLanguage::Kotlin | TargetLanguage::Java => {
+                let mut path: PathBuf = self.package_segments(identifier).iter().collect();
+                let extension = match self.language {
+                    TargetLanguage::Kotlin => "kt",
+                    _ => "java",
+                };
+                path.push(format!("{}.{}", name, extension));
+                path
+            }
+            TargetLanguage::Swift | TargetLanguage::Dart => {
+                let mut path: PathBuf = identifier
+                    .path()
+                    .segments
+                    .split_last()
+                    .map(|(_, module)| module.iter().collect())
+                    .unwrap_or_default();
+                match self.language {
+                    TargetLanguage::Swift => path.push(format!("{}.swift", name)),
+                    _ => path.push(format!("{}.dart", snake_case(name))),
+                }
+                path
+            }
+        }
+    }
+}
+
+/// First line of every generated file.
+pub(crate) fn generated_header(identifier: &TypeDefinitionIdentifier) -> String {
+    format!("// Generated by open-ams from {}, do not edit.", identifier)
+}
+
+/// Doc comment of a declaration indented by `indent`: KDoc and Javadoc blocks, DocC and dartdoc
+/// line comments. Empty without a description.
+pub(crate) fn doc_comment(
+    language: &TargetLanguage,
+    description: Option<&str>,
+    indent: &str,
+) -> String {
+    let Some(description) = description.map(str::trim).filter(|d| !d.is_empty()) else {
+        return String::new();
+    };
+    let comment_line = |prefix: &str, line: &str| match line.is_empty() {
+        true => format!("{}{}\n", indent, prefix),
+        false => format!("{}{} {}\n", indent, prefix, line),
+    };
+    match language {
+        TargetLanguage::Kotlin | TargetLanguage::Java => {
+            let lines: Vec<String> = description
+                .lines()
+                .map(|line| line.trim_end().replace("*/", "* /"))
+                .collect();
+            match lines.as_slice() {
+                [line] => format!("{}/** {} */\n", indent, line),
+                _ => {
+                    let body: String = lines.iter().map(|line| comment_line(" *", line)).collect();
+                    format!("{}/**\n{}{} */\n", indent, body, indent)
+                }
+            }
+        }
+        TargetLanguage::Swift | TargetLanguage::Dart => description
+            .lines()
+            .map(|line| comment_line("///", line.trim_end()))
+            .collect(),
+    }
+}
+
+/// Javadoc of a deprecated declaration also carries a `@deprecated` tag, the annotation alone
+/// not telling what to use instead.
+pub(crate) fn documentation(
+    language: &TargetLanguage,
+    description: Option<&str>,
+    deprecation: Option<&Deprecation>,
+) -> Option<String> {
+    let (TargetLanguage::Java, Some(deprecation)) = (language, deprecation) else {
+        return description.map(str::to_string);
+    };
+    let tag = format!("@deprecated {}", deprecation.message());
+    Some(match description.map(str::trim_end) {
+        Some(description) => format!("{}\n\n{}", description, tag),
+        None => tag,
+    })
+}
+
+/// Native deprecation annotation on its own line, empty when the declaration isn't deprecated.
+pub(crate) fn deprecation_annotation(
+    language: &TargetLanguage,
+    deprecation: Option<&Deprecation>,
+    indent: &str,
+) -> String {
+    let Some(deprecation) = deprecation else {
+        return String::new();
+    };
+    let message = literal(language, None, &deprecation.message());
+    let annotation = match language {
+        TargetLanguage::Kotlin => format!("@Deprecated({})", message),
+        TargetLanguage::Java => match deprecation.since() {
+            Some(since) => format!(
+                "@Deprecated(since = {})",
+                literal(language, None, &since.to_string())
+            ),
+            None => "@Deprecated".to_string(),
+        },
+        TargetLanguage::Swift => format!("@available(*, deprecated, message: {})", message),
+        TargetLanguage::Dart => format!("@Deprecated({})", message),
+    };
+    format!("{}{}\n", indent, annotation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EnumValue, TypeConstraints};
+    use crate::source::entity::Version;
+    use crate::util::{example_environment, std_type, type_identifier};
+    use std::path::Path;
+    use std::str::FromStr;
+
+    fn login_method_environment() -> Environment {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_enum(
+            type_identifier("tuna-bank:client-app", "common/LoginMethod"),
+            None,
+            vec![EnumValue::new("sms", None)],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        env
+    }
+
+    #[test]
+    fn test_doc_comment() {
+        assert_eq!(doc_comment(&TargetLanguage::Kotlin, None, ""), "");
+        assert_eq!(
+            doc_comment(&TargetLanguage::Java, Some("Login method."), "    "),
+            "    /** Login method. */\n"
+        );
+        assert_eq!(
+            doc_comment(
+                &TargetLanguage::Kotlin,
+                Some("Login method.\n\nSee */auth*/.\n"),
+                ""
+            ),
+            "/**\n * Login method.\n *\n * See * /auth* /.\n */\n"
+        );
+        assert_eq!(
+            doc_comment(&TargetLanguage::Dart, Some("Login method.\n\nSee"), "  "),
+            "  /// Login method.\n  ///\n  /// See\n"
+        );
+    }
+
+    #[test]
+    fn test_deprecation_annotation() {
+        let deprecation = Deprecation::new(
+            Some(Version::from_str("1.2.0").unwrap()),
+            Some("Use \"method\" instead"),
+            None,
+        );
+        let annotation = |language| deprecation_annotation(&language, Some(&deprecation), "    ");
+
+        assert_eq!(
+            annotation(TargetLanguage::Kotlin),
+            "    @Deprecated(\"Deprecated since 1.2.0: Use \\\"method\\\" instead.\")\n"
+        );
+        assert_eq!(
+            annotation(TargetLanguage::Java),
+            "    @Deprecated(since = \"1.2.0\")\n"
+        );
+        assert_eq!(
+            annotation(TargetLanguage::Swift),
+            "    @available(*, deprecated, message: \"Deprecated since 1.2.0: Use \\\"method\\\" instead.\")\n"
+        );
+        assert_eq!(
+            annotation(TargetLanguage::Dart),
+            "    @Deprecated('Deprecated since 1.2.0: Use \"method\" instead.')\n"
+        );
+        assert_eq!(
+            deprecation_annotation(&TargetLanguage::Kotlin, None, ""),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_file_paths() {
+        let env = login_method_environment();
+        let path = |language: TargetLanguage| {
+            let files = CodeGenerator::of(&env, language).export().unwrap();
+            files[0].path().to_path_buf()
+        };
+
+        assert_eq!(
+            path(TargetLanguage::Kotlin),
+            Path::new("tuna_bank/client_app/common/LoginMethod.kt")
+        );
+        assert_eq!(
+            path(TargetLanguage::Java),
+            Path::new("tuna_bank/client_app/common/LoginMethod.java")
+        );
+        assert_eq!(
+            path(TargetLanguage::Swift),
+            Path::new("common/LoginMethod.swift")
+        );
+        assert_eq!(
+            path(TargetLanguage::Dart),
+            Path::new("common/login_method.dart")
+        );
+    }
+
+    #[test]
+    fn test_only_target_project_types() {
+        let env = example_environment();
+
+        assert_eq!(
+            CodeGenerator::of(&env, TargetLanguage::Kotlin)
+                .export()
+                .unwrap(),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_collection_type_name() {
+        let mut env = login_method_environment();
+        let login_method = type_identifier("tuna-bank:client-app", "common/LoginMethod");
+        let list = TypeDefinitionIdentifier::collection(CollectionKind::List, &login_method);
+        let map = TypeDefinitionIdentifier::collection(CollectionKind::Map, &list);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            login_method.clone(),
+        ));
+        env.push_type_definition(TypeDefinition::new_collection(CollectionKind::Map, list));
+        let type_name = |language: TargetLanguage| {
+            CodeGenerator::of(&env, language).type_name(&map, &login_method)
+        };
+
+        assert_eq!(
+            type_name(TargetLanguage::Kotlin),
+            "kotlin.collections.Map<kotlin.String, kotlin.collections.List<LoginMethod>>"
+        );
+        assert_eq!(
+            type_name(TargetLanguage::Java),
+            "java.util.Map<java.lang.String, java.util.List<LoginMethod>>"
+        );
+        assert_eq!(type_name(TargetLanguage::Swift), "[String: [LoginMethod]]");
+        assert_eq!(
+            type_name(TargetLanguage::Dart),
+            "Map<String, List<LoginMethod>>"
+        );
+        assert_eq!(
+            CodeGenerator::of(&env, TargetLanguage::Dart).referenced_declarations(&map),
+            vec![&login_method]
+        );
+    }
+
+    #[test]
+    fn test_type_name() {
+        let env = login_method_environment();
+        let generator = CodeGenerator::of(&env, TargetLanguage::Swift);
+        let login_method = type_identifier("tuna-bank:client-app", "common/LoginMethod");
+
+        assert_eq!(
+            generator.type_name(&login_method, &login_method),
+            "LoginMethod"
+        );
+        assert_eq!(
+            generator.type_name(&std_type("Number"), &login_method),
+            "Double"
+        );
+
+        let kotlin = CodeGenerator::of(&env, TargetLanguage::Kotlin);
+        let cart = type_identifier("tuna-bank:client-app", "cart/Cart");
+        assert_eq!(
+            kotlin.type_name(&login_method, &cart),
+            "tuna_bank.client_app.common.LoginMethod"
+        );
+        assert_eq!(
+            kotlin.type_name(&login_method, &login_method),
+            "LoginMethod"
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use std::fmt::Write;
+
+use crate::codegen::code_generator::{
+    deprecation_annotation, doc_comment, documentation, generated_header,
+};
+use crate::codegen::CodeGenerator;
+use crate::entity::{EnumTypeDefinition, EnumValue, PrimitiveType};
+use crate::export::naming::{lower_camel_case, screaming_snake_case};
+use crate::source::entity::TargetLanguage;
+
+/// Native enum carrying the declared value of every case, so that it can be tracked as is.
+pub(crate) fn render_enum(generator: &CodeGenerator, definition: &EnumTypeDefinition) -> String {
+    let identifier = definition.identifier();
+    let name = identifier.name();
+    let value_type = generator.enum_value_type_name(identifier);
+    let primitive_type = generator.primitive_type(identifier);
+    let literal = |value: &EnumValue| literal(generator.language(), primitive_type, value.value());
+
+    let language = generator.language();
+    let documentation = documentation(language, definition.description(), definition.deprecation());
+    let declaration_prefix = format!(
+        "{}{}",
+        doc_comment(language, documentation.as_deref(), ""),
+        deprecation_annotation(language, definition.deprecation(), "")
+    );
+
+    let mut out = String::new();
+    writeln!(out, "{}", generated_header(identifier)).unwrap();
+    match language {
+        TargetLanguage::Kotlin => {
+            writeln!(out, "package {}\n", generator.package(identifier)).unwrap();
+            write!(out, "{}", declaration_prefix).unwrap();
+            writeln!(out, "enum class {}(val value: {}) {{", name, value_type).unwrap();
+            let cases: Vec<String> = definition
+                .values()
+                .iter()
+                .map(|value| {
+                    format!(
+                        "{}    {}({})",
+                        doc_comment(language, value.description(), "    "),
+                        case_name(language, value.value()),
+                        literal(value)
+                    )
+                })
+                .collect();
+            writeln!(out, "{};", cases.join(",\n")).unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+        TargetLanguage::Java => {
+            writeln!(out, "package {};\n", generator.package(identifier)).unwrap();
+            write!(out, "{}", declaration_prefix).unwrap();
+            writeln!(out, "public enum {} {{", name).unwrap();
+            let cases: Vec<String> = definition
+                .values()
+                .iter()
+                .map(|value| {
+                    format!(
+                        "{}    {}({})",
+                        doc_comment(language, value.description(), "    "),
+                        case_name(language, value.value()),
+                        literal(value)
+                    )
+                })
+                .collect();
+            writeln!(out, "{};\n", cases.join(",\n")).unwrap();
+            writeln!(out, "    private final {} value;\n", value_type).unwrap();
+            writeln!(out, "    {}({} value) {{", name, value_type).unwrap();
+            writeln!(out, "        this.value = value;").unwrap();
+            writeln!(out, "    }}\n").unwrap();
+            writeln!(out, "    public {} getValue() {{", value_type).unwrap();
+            writeln!(out, "        return value;").unwrap();
+            writeln!(out, "    }}").unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+        TargetLanguage::Swift => {
+            writeln!(out).unwrap();
+            write!(out, "{}", declaration_prefix).unwrap();
+            writeln!(out, "public enum {}: {}, Codable {{", name, value_type).unwrap();
+            for value in definition.values() {
+                write!(
+                    out,
+                    "{}",
+                    doc_comment(language, value.description(), "    ")
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    "    case {} = {}",
+                    case_name(language, value.value()),
+                    literal(value)
+                )
+                .unwrap();
+            }
+            writeln!(out, "}}").unwrap();
+        }
+        TargetLanguage::Dart => {
+            writeln!(out).unwrap();
+            write!(out, "{}", declaration_prefix).unwrap();
+            writeln!(out, "enum {} {{", name).unwrap();
+            let cases: Vec<String> = definition
+                .values()
+                .iter()
+                .map(|value| {
+                    format!(
+                        "{}  {}({})",
+                        doc_comment(language, value.description(), "  "),
+                        case_name(language, value.value()),
+                        literal(value)
+                    )
+                })
+                .collect();
+            writeln!(out, "{};\n", cases.join(",\n")).unwrap();
+            writeln!(out, "  const {}(this.value);\n", name).unwrap();
+            writeln!(out, "  final {} value;", value_type).unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+    }
+    out
+}
+
+/// Name of the case of `value`, in screaming snake case in Kotlin and Java and in lower camel
+/// case in Swift and Dart. Reserved words are escaped with backticks in Swift and suffixed with
+/// `_` in the other languages.
+pub(crate) fn case_name(language: &TargetLanguage, value: &str) -> String {
+    let name = match language {
+        TargetLanguage::Kotlin | TargetLanguage::Java => screaming_snake_case(value),
+        TargetLanguage::Swift | TargetLanguage::Dart => lower_camel_case(value),
+    };
+    if !reserved_words(language).contains(&name.as_str()) {
+        return name;
+    }
+    match language {
+        TargetLanguage::Swift => format!("`{}`", name),
+        _ => format!("{}_", name),
+    }
+}
+
+/// Words that can't name an enum case. Kotlin and Java keywords are all lowercase, so only `_`
+/// can clash with a screaming snake case name. Dart enums also reserve the names of their own
+/// members, including the generated `value`.
+fn reserved_words(language: &TargetLanguage) -> &'static [&'static str] {
+    match language {
+        TargetLanguage::Kotlin | TargetLanguage::Java => &["_"],
+        TargetLanguage::Swift => &[
+            "Any",
+            "Self",
+            "as",
+            "associatedtype",
+            "await",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "continue",
+            "default",
+            "defer",
+            "deinit",
+            "do",
+            "else",
+            "enum",
+            "extension",
+            "fallthrough",
+            "false",
+            "fileprivate",
+            "for",
+            "func",
+            "guard",
+            "if",
+            "import",
+            "in",
+            "init",
+            "inout",
+            "internal",
+            "is",
+            "let",
+            "nil",
+            "open",
+            "operator",
+            "private",
+            "precedencegroup",
+            "protocol",
+            "public",
+            "repeat",
+            "rethrows",
+            "return",
+            "self",
+            "static",
+            "struct",
+            "subscript",
+            "super",
+            "switch",
+            "throw",
+            "throws",
+            "true",
+            "try",
+            "typealias",
+            "var",
+            "where",
+            "while",
+        ],
+        TargetLanguage::Dart => &[
+            "assert",
+            "break",
+            "case",
+            "catch",
+            "class",
+            "const",
+            "continue",
+            "default",
+            "do",
+            "else",
+            "enum",
+            "extends",
+            "false",
+            "final",
+            "finally",
+            "for",
+            "hashCode",
+            "if",
+            "in",
+            "index",
+            "is",
+            "new",
+            "noSuchMethod",
+            "null",
+            "rethrow",
+            "return",
+            "runtimeType",
+            "super",
+            "switch",
+            "this",
+            "throw",
+            "toString",
+            "true",
+            "try",
+            "value",
+            "values",
+            "var",
+            "void",
+            "while",
+            "with",
+        ],
+    }
+}
+
+/// String or number literal of `value` in the target language, Java longs taking an `L` suffix
+/// as an `int` literal doesn't box into a `Long`.
+pub(crate) fn literal(
+    language: &TargetLanguage,
+    primitive_type: Option<PrimitiveType>,
+    value: &str,
+) -> String {
+    match primitive_type {
+        Some(PrimitiveType::Long) if *language == TargetLanguage::Java => format!("{}L", value),
+        Some(PrimitiveType::Integer | PrimitiveType::Long | PrimitiveType::Boolean) => {
+            value.to_string()
+        }
+        Some(PrimitiveType::Float) => value
+            .parse::<f64>()
+            .map(|number| format!("{:?}", number))
+            .unwrap_or_else(|_| value.to_string()),
+        _ => {
+            let escaped = value.replace('\\', "\\\\");
+            match language {
+                TargetLanguage::Dart => {
+                    format!("'{}'", escaped.replace('\'', "\\'").replace('$', "\\$"))
+                }
+                TargetLanguage::Kotlin => {
+                    format!("\"{}\"", escaped.replace('"', "\\\"").replace('$', "\\$"))
+                }
+                TargetLanguage::Java | TargetLanguage::Swift => {
+                    format!("\"{}\"", escaped.replace('"', "\\\""))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{Environment, TypeConstraints, TypeDefinition};
+    use crate::util::{example_environment, std_type, type_identifier};
+
+    fn login_method_environment() -> Environment {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_enum(
+            type_identifier("tuna-bank:client-app", "common/LoginMethod"),
+            None,
+            vec![
+                EnumValue::new("sms", Some("One-time code sent by SMS")),
+                EnumValue::new("password", None),
+                EnumValue::new("biometric", None),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        env
+    }
+
+    fn render(language: TargetLanguage) -> String {
+        let env = login_method_environment();
+        let files = CodeGenerator::of(&env, language).export().unwrap();
+        files[0].content().to_string()
+    }
+
+    #[test]
+    fn test_kotlin() {
+        assert_eq!(
+            render(TargetLanguage::Kotlin),
+            "// Generated by open-ams from tuna-bank:client-app/common/LoginMethod, do not edit.
+package tuna_bank.client_app.common
+
+enum class LoginMethod(val value: kotlin.String) {
+    /** One-time code sent by SMS */
+    SMS(\"sms\"),
+    PASSWORD(\"password\"),
+    BIOMETRIC(\"biometric\");
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_java() {
+        assert_eq!(
+            render(TargetLanguage::Java),
+            "// Generated by open-ams from tuna-bank:client-app/common/LoginMethod, do not edit.
+package tuna_bank.client_app.common;
+
+public enum LoginMethod {
+    /** One-time code sent by SMS */
+    SMS(\"sms\"),
+    PASSWORD(\"password\"),
+    BIOMETRIC(\"biometric\");
+
+    private final java.lang.String value;
+
+    LoginMethod(java.lang.String value) {
+        this.value = value;
+    }
+
+    public java.lang.String getValue() {
+        return value;
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_swift() {
+        assert_eq!(
+            render(TargetLanguage::Swift),
+            "// Generated by open-ams from tuna-bank:client-app/common/LoginMethod, do not edit.
+
+public enum LoginMethod: String, Codable {
+    /// One-time code sent by SMS
+    case sms = \"sms\"
+    case password = \"password\"
+    case biometric = \"biometric\"
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_dart() {
+        assert_eq!(
+            render(TargetLanguage::Dart),
+            "// Generated by open-ams from tuna-bank:client-app/common/LoginMethod, do not edit.
+
+enum LoginMethod {
+  /// One-time code sent by SMS
+  sms('sms'),
+  password('password'),
+  biometric('biometric');
+
+  const LoginMethod(this.value);
+
+  final String value;
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_reserved_case_names() {
+        assert_eq!(case_name(&TargetLanguage::Swift, "default"), "`default`");
+        assert_eq!(case_name(&TargetLanguage::Swift, "sms"), "sms");
+        assert_eq!(case_name(&TargetLanguage::Dart, "new"), "new_");
+        assert_eq!(case_name(&TargetLanguage::Dart, "values"), "values_");
+        assert_eq!(case_name(&TargetLanguage::Kotlin, "default"), "DEFAULT");
+        assert_eq!(case_name(&TargetLanguage::Java, "-"), "__");
+    }
+
+    #[test]
+    fn test_java_long() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_enum(
+            type_identifier("tuna-bank:client-app", "common/Tier"),
+            Some(std_type("Long")),
+            vec![EnumValue::new("1", None), EnumValue::new("2", None)],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let files = CodeGenerator::of(&env, TargetLanguage::Java)
+            .export()
+            .unwrap();
+
+        assert!(files[0].content().contains(
+            "public enum Tier {
+    _1(1L),
+    _2(2L);
+
+    private final java.lang.Long value;
+
+    Tier(java.lang.Long value) {"
+        ));
+    }
+
+    #[test]
+    fn test_literal() {
+        assert_eq!(
+            literal(&TargetLanguage::Kotlin, None, "$x \"y\""),
+            "\"\\$x \\\"y\\\"\""
+        );
+        assert_eq!(literal(&TargetLanguage::Dart, None, "it's"), "'it\\'s'");
+        assert_eq!(
+            literal(&TargetLanguage::Swift, Some(PrimitiveType::Float), "2"),
+            "2.0"
+        );
+    }
+}
@@ -0,0 +1,6 @@
+mod code_generator;
+mod enums;
+mod objects;
+
+pub use code_generator::*;
+pub(crate) use enums::case_name;
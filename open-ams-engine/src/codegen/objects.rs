@@ -0,0 +1,637 @@
+use std::fmt::Write;
+
+use serde_json::Value;
+
+use crate::codegen::code_generator::{
+    deprecation_annotation, doc_comment, documentation, generated_header,
+};
+use crate::codegen::enums::{case_name, literal};
+use crate::codegen::CodeGenerator;
+use crate::entity::{Deprecation, ObjectTypeDefinition, TypeDefinitionIdentifier};
+use crate::export::naming::sanitize_identifier;
+use crate::source::entity::TargetLanguage;
+
+/// Immutable data class, record or struct with a property per field of the object. Optional
+/// fields are nullable unless their default can be written as a literal of the language.
+pub(crate) fn render_object(
+    generator: &CodeGenerator,
+    definition: &ObjectTypeDefinition,
+) -> String {
+    let identifier = definition.identifier();
+    let name = identifier.name();
+    let fields: Vec<Property> = definition
+        .fields()
+        .iter()
+        .map(|field| Property {
+            name: sanitize_identifier(field.name()),
+            type_name: generator.type_name(field.type_ref(), identifier),
+            description: field.description().map(str::to_string),
+            deprecation: field.deprecation().cloned(),
+            required: field.required(),
+            default: field.default().and_then(|default| {
+                default_literal(generator, field.type_ref(), identifier, default)
+            }),
+        })
+        .collect();
+
+    let language = generator.language();
+    let deprecation = definition.deprecation();
+    let declaration_prefix = format!(
+        "{}{}",
+        doc_comment(language, definition.description(), ""),
+        deprecation_annotation(language, deprecation, "")
+    );
+
+    let mut out = String::new();
+    writeln!(out, "{}", generated_header(identifier)).unwrap();
+    match language {
+        TargetLanguage::Kotlin => {
+            writeln!(out, "package {}\n", generator.package(identifier)).unwrap();
+            write!(out, "{}", declaration_prefix).unwrap();
+            if fields.is_empty() {
+                writeln!(out, "class {}", name).unwrap();
+                return out;
+            }
+            writeln!(out, "data class {}(", name).unwrap();
+            for field in &fields {
+                write!(out, "{}", field.preamble(language, "    ")).unwrap();
+                writeln!(
+                    out,
+                    "    val {}: {},",
+                    field.name,
+                    field.declaration("?", "null")
+                )
+                .unwrap();
+            }
+            writeln!(out, ")").unwrap();
+        }
+        TargetLanguage::Java => {
+            writeln!(out, "package {};\n", generator.package(identifier)).unwrap();
+            let components: Vec<String> = fields
+                .iter()
+                .map(|field| {
+                    format!(
+                        "{}    {} {}",
+                        deprecation_annotation(language, field.deprecation.as_ref(), "    "),
+                        field.type_name,
+                        field.name
+                    )
+                })
+                .collect();
+            let record_doc = record_doc(definition, &fields);
+            let documentation = documentation(language, record_doc.as_deref(), deprecation);
+            write!(
+                out,
+                "{}{}",
+                doc_comment(language, documentation.as_deref(), ""),
+                deprecation_annotation(language, deprecation, "")
+            )
+            .unwrap();
+            writeln!(out, "public record {}(", name).unwrap();
+            if !components.is_empty() {
+                writeln!(out, "{}", components.join(",\n")).unwrap();
+            }
+            let defaults: Vec<String> = fields
+                .iter()
+                .filter(|field| !field.required)
+                .filter_map(|field| {
+                    let default = field.default.as_ref()?;
+                    Some(format!(
+                        "        {} = java.util.Objects.requireNonNullElse({}, {});",
+                        field.name, field.name, default
+                    ))
+                })
+                .collect();
+            if defaults.is_empty() {
+                writeln!(out, ") {{}}").unwrap();
+            } else {
+                writeln!(out, ") {{").unwrap();
+                writeln!(out, "    public {} {{", name).unwrap();
+                writeln!(out, "{}", defaults.join("\n")).unwrap();
+                writeln!(out, "    }}").unwrap();
+                writeln!(out, "}}").unwrap();
+            }
+        }
+        TargetLanguage::Swift => {
+            writeln!(out).unwrap();
+            write!(out, "{}", declaration_prefix).unwrap();
+            writeln!(out, "public struct {}: Codable {{", name).unwrap();
+            for field in &fields {
+                write!(out, "{}", field.preamble(language, "    ")).unwrap();
+                writeln!(
+                    out,
+                    "    public let {}: {}",
+                    field.name,
+                    field.nullable_type("?")
+                )
+                .unwrap();
+            }
+            let parameters: Vec<String> = fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, field.declaration("?", "nil")))
+                .collect();
+            writeln!(out).unwrap();
+            writeln!(out, "    public init({}) {{", parameters.join(", ")).unwrap();
+            for field in &fields {
+                writeln!(out, "        self.{} = {}", field.name, field.name).unwrap();
+            }
+            writeln!(out, "    }}").unwrap();
+            writeln!(out, "}}").unwrap();
+        }
+        TargetLanguage::Dart => {
+            let field_types: Vec<_> = definition.fields().iter().map(|f| f.type_ref()).collect();
+            let imports = generator.dart_imports(identifier, &field_types);
+            if !imports.is_empty() {
+                writeln!(out).unwrap();
+            }
+            for import in imports {
+                writeln!(out, "import '{}';", import).unwrap();
+            }
+            writeln!(out).unwrap();
+            write!(out, "{}", declaration_prefix).unwrap();
+            writeln!(out, "class {} {{", name).unwrap();
+            for field in &fields {
+                write!(out, "{}", field.preamble(language, "  ")).unwrap();
+                writeln!(out, "  final {} {};", field.nullable_type("?"), field.name).unwrap();
+            }
+            writeln!(out).unwrap();
+            if fields.is_empty() {
+                writeln!(out, "  const {}();", name).unwrap();
+            } else {
+                writeln!(out, "  const {}({{", name).unwrap();
+                for field in &fields {
+                    match (field.required, &field.default) {
+                        (true, _) => writeln!(out, "    required this.{},", field.name),
+                        (false, Some(default)) => {
+                            writeln!(out, "    this.{} = {},", field.name, default)
+                        }
+                        (false, None) => writeln!(out, "    this.{},", field.name),
+                    }
+                    .unwrap();
+                }
+                writeln!(out, "  }});").unwrap();
+            }
+            writeln!(out, "}}").unwrap();
+        }
+    }
+    out
+}
+
+/// Javadoc of a record, records documenting their components with `@param` tags. Optional
+/// components without a default are documented as nullable.
+fn record_doc(definition: &ObjectTypeDefinition, fields: &[Property]) -> Option<String> {
+    let mut sections: Vec<String> = definition
+        .description()
+        .map(|description| description.trim_end().to_string())
+        .into_iter()
+        .collect();
+    let tags: Vec<String> = fields
+        .iter()
+        .filter_map(|field| {
+            let description = field.description.as_deref().map(str::trim_end);
+            let nullable = (!field.required && field.default.is_none()).then_some("May be null.");
+            let text: Vec<&str> = description.into_iter().chain(nullable).collect();
+            match text.is_empty() {
+                true => None,
+                false => Some(format!("@param {} {}", field.name, text.join(" "))),
+            }
+        })
+        .collect();
+    if !tags.is_empty() {
+        sections.push(tags.join("\n"));
+    }
+    match sections.is_empty() {
+        true => None,
+        false => Some(sections.join("\n\n")),
+    }
+}
+
+struct Property {
+    name: String,
+    type_name: String,
+    description: Option<String>,
+    deprecation: Option<Deprecation>,
+    required: bool,
+    /// Literal of the default in the target language.
+    default: Option<String>,
+}
+
+impl Property {
+    /// Doc comment and deprecation annotation preceding the property.
+    fn preamble(&self, language: &TargetLanguage, indent: &str) -> String {
+        format!(
+            "{}{}",
+            doc_comment(language, self.description.as_deref(), indent),
+            deprecation_annotation(language, self.deprecation.as_ref(), indent)
+        )
+    }
+
+    /// Type of the property, marked with `nullable` when it may be missing without a default.
+    fn nullable_type(&self, nullable: &str) -> String {
+        match self.required || self.default.is_some() {
+            true => self.type_name.clone(),
+            false => format!("{}{}", self.type_name, nullable),
+        }
+    }
+
+    /// Type of the constructor parameter along with its default value, `null` being the default
+    /// of optional fields without one.
+    fn declaration(&self, nullable: &str, null: &str) -> String {
+        match (self.required, &self.default) {
+            (true, _) => self.type_name.clone(),
+            (false, Some(default)) => format!("{} = {}", self.type_name, default),
+            (false, None) => format!("{} = {}", self.nullable_type(nullable), null),
+        }
+    }
+}
+
+/// Literal of a string, number, boolean or enum default, enums being referred to by their case.
+/// Defaults of types bound to other native types, such as URLs or dates, have no literal.
+fn default_literal(
+    generator: &CodeGenerator,
+    type_ref: &TypeDefinitionIdentifier,
+    context: &TypeDefinitionIdentifier,
+    default: &Value,
+) -> Option<String> {
+    let value = match default {
+        Value::String(value) => value.clone(),
+        Value::Number(number) => number.to_string(),
+        Value::Bool(value) => value.to_string(),
+        _ => return None,
+    };
+    if generator.env().enum_definition(type_ref).is_some() {
+        let type_name = generator.type_name(type_ref, context);
+        let case_name = case_name(generator.language(), &value);
+        return Some(match generator.language() {
+            TargetLanguage::Swift => format!(".{}", case_name),
+            _ => format!("{}.{}", type_name, case_name),
+        });
+    }
+    if !generator
+        .language()
+        .has_literals(&generator.type_name(type_ref, context))
+    {
+        return None;
+    }
+    let primitive_type = generator.primitive_type(type_ref)?;
+    Some(literal(generator.language(), Some(primitive_type), &value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{
+        Deprecation, EnumValue, Environment, ObjectField, TypeConstraints, TypeDefinition,
+    };
+    use crate::source::entity::Version;
+    use crate::util::{example_environment, std_type, type_identifier};
+    use serde_json::json;
+    use std::str::FromStr;
+
+    fn cart_environment() -> Environment {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_enum(
+            type_identifier("tuna-bank:client-app", "common/Currency"),
+            None,
+            vec![EnumValue::new("EUR", None), EnumValue::new("USD", None)],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        env.push_type_definition(TypeDefinition::new_object(
+            type_identifier("tuna-bank:client-app", "cart/CartItem"),
+            vec![
+                ObjectField::new(
+                    "currency",
+                    type_identifier("tuna-bank:client-app", "common/Currency"),
+                ),
+                ObjectField::new("price", std_type("Float")),
+                ObjectField::new("quantity", std_type("Integer")),
+                ObjectField::new("sku", std_type("String")),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        env
+    }
+
+    fn render(language: TargetLanguage) -> String {
+        let env = cart_environment();
+        let files = CodeGenerator::of(&env, language).export().unwrap();
+        files[1].content().to_string()
+    }
+
+    #[test]
+    fn test_kotlin() {
+        assert_eq!(
+            render(TargetLanguage::Kotlin),
+            "// Generated by open-ams from tuna-bank:client-app/cart/CartItem, do not edit.
+package tuna_bank.client_app.cart
+
+data class CartItem(
+    val currency: tuna_bank.client_app.common.Currency,
+    val price: kotlin.Double,
+    val quantity: kotlin.Int,
+    val sku: kotlin.String,
+)
+"
+        );
+    }
+
+    #[test]
+    fn test_java() {
+        assert_eq!(
+            render(TargetLanguage::Java),
+            "// Generated by open-ams from tuna-bank:client-app/cart/CartItem, do not edit.
+package tuna_bank.client_app.cart;
+
+public record CartItem(
+    tuna_bank.client_app.common.Currency currency,
+    java.lang.Double price,
+    java.lang.Integer quantity,
+    java.lang.String sku
+) {}
+"
+        );
+    }
+
+    #[test]
+    fn test_swift() {
+        assert_eq!(
+            render(TargetLanguage::Swift),
+            "// Generated by open-ams from tuna-bank:client-app/cart/CartItem, do not edit.
+
+public struct CartItem: Codable {
+    public let currency: Currency
+    public let price: Double
+    public let quantity: Int
+    public let sku: String
+
+    public init(currency: Currency, price: Double, quantity: Int, sku: String) {
+        self.currency = currency
+        self.price = price
+        self.quantity = quantity
+        self.sku = sku
+    }
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_dart() {
+        assert_eq!(
+            render(TargetLanguage::Dart),
+            "// Generated by open-ams from tuna-bank:client-app/cart/CartItem, do not edit.
+
+import '../common/currency.dart';
+
+class CartItem {
+  final Currency currency;
+  final double price;
+  final int quantity;
+  final String sku;
+
+  const CartItem({
+    required this.currency,
+    required this.price,
+    required this.quantity,
+    required this.sku,
+  });
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let mut env = cart_environment();
+        env.push_type_definition(TypeDefinition::new_object(
+            type_identifier("tuna-bank:client-app", "common/Filter"),
+            vec![
+                ObjectField::new_optional(
+                    "currency",
+                    type_identifier("tuna-bank:client-app", "common/Currency"),
+                    Some(json!("USD")),
+                ),
+                ObjectField::new_optional("page", std_type("Integer"), Some(json!(1))),
+                ObjectField::new_optional("query", std_type("String"), None),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let render = |language| {
+            let files = CodeGenerator::of(&env, language).export().unwrap();
+            let filter = files.iter().find(|file| {
+                let path = file.path().to_string_lossy().to_lowercase();
+                path.ends_with("filter.kt")
+                    || path.ends_with("filter.java")
+                    || path.ends_with("filter.swift")
+                    || path.ends_with("filter.dart")
+            });
+            filter.unwrap().content().to_string()
+        };
+
+        assert!(render(TargetLanguage::Kotlin).contains(
+            "    val currency: Currency = Currency.USD,
+    val page: kotlin.Int = 1,
+    val query: kotlin.String? = null,
+"
+        ));
+        assert!(render(TargetLanguage::Java).contains(
+            "/** @param query May be null. */
+public record Filter(
+    Currency currency,
+    java.lang.Integer page,
+    java.lang.String query
+) {
+    public Filter {
+        currency = java.util.Objects.requireNonNullElse(currency, Currency.USD);
+        page = java.util.Objects.requireNonNullElse(page, 1);
+    }
+}
+"
+        ));
+        assert!(render(TargetLanguage::Swift).contains(
+            "    public let query: String?
+
+    public init(currency: Currency = .usd, page: Int = 1, query: String? = nil) {"
+        ));
+        assert!(render(TargetLanguage::Dart).contains(
+            "  final String? query;
+
+  const Filter({
+    this.currency = Currency.usd,
+    this.page = 1,
+    this.query,
+  });"
+        ));
+    }
+
+    #[test]
+    fn test_defaults_without_literals() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_object(
+            type_identifier("tuna-bank:client-app", "common/Link"),
+            vec![
+                ObjectField::new_optional(
+                    "target",
+                    std_type("Url"),
+                    Some(json!("https://example.com")),
+                ),
+                ObjectField::new_optional("wait", std_type("Duration"), Some(json!("PT5M"))),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let render = |language| {
+            let files = CodeGenerator::of(&env, language).export().unwrap();
+            let link = files.iter().find(|file| {
+                let stem = file
+                    .path()
+                    .file_stem()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_lowercase();
+                stem == "link"
+            });
+            link.unwrap().content().to_string()
+        };
+
+        assert!(render(TargetLanguage::Kotlin).contains(
+            "    val target: java.net.URI? = null,
+    val wait: kotlin.time.Duration? = null,
+"
+        ));
+        assert!(render(TargetLanguage::Swift)
+            .contains("    public init(target: URL? = nil, wait: String = \"PT5M\") {"));
+        assert!(render(TargetLanguage::Dart).contains(
+            "  const Link({
+    this.target,
+    this.wait,
+  });"
+        ));
+    }
+
+    #[test]
+    fn test_descriptions() {
+        let mut env = example_environment();
+        env.push_type_definition(
+            TypeDefinition::new_object(
+                type_identifier("tuna-bank:client-app", "cart/Item"),
+                vec![ObjectField::new("sku", std_type("String"))
+                    .with_description(Some("Stock keeping unit."))],
+                None,
+                None,
+                TypeConstraints::default(),
+            )
+            .with_description(Some("Item of a cart.\n\nPriced per unit.")),
+        );
+        let render = |language| {
+            let files = CodeGenerator::of(&env, language).export().unwrap();
+            let item = files.iter().find(|file| {
+                let stem = file
+                    .path()
+                    .file_stem()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_lowercase();
+                stem == "item"
+            });
+            item.unwrap().content().to_string()
+        };
+
+        assert!(render(TargetLanguage::Kotlin).contains(
+            "/**
+ * Item of a cart.
+ *
+ * Priced per unit.
+ */
+data class Item(
+    /** Stock keeping unit. */
+    val sku: kotlin.String,
+)"
+        ));
+        assert!(render(TargetLanguage::Java).contains(
+            "/**
+ * Item of a cart.
+ *
+ * Priced per unit.
+ *
+ * @param sku Stock keeping unit.
+ */
+public record Item("
+        ));
+        assert!(render(TargetLanguage::Swift).contains(
+            "/// Item of a cart.
+///
+/// Priced per unit.
+public struct Item: Codable {
+    /// Stock keeping unit.
+    public let sku: String"
+        ));
+        assert!(render(TargetLanguage::Dart).contains(
+            "/// Item of a cart.
+///
+/// Priced per unit.
+class Item {
+  /// Stock keeping unit.
+  final String sku;"
+        ));
+    }
+
+    #[test]
+    fn test_deprecations() {
+        let mut env = example_environment();
+        let string = std_type("String");
+        env.push_type_definition(
+            TypeDefinition::new_object(
+                type_identifier("tuna-bank:client-app", "cart/Item"),
+                vec![
+                    ObjectField::new("code", string.clone()).with_deprecation(Some(
+                        Deprecation::new(None, None, Some("cart/Item.sku".to_string())),
+                    )),
+                    ObjectField::new("sku", string),
+                ],
+                None,
+                None,
+                TypeConstraints::default(),
+            )
+            .with_deprecation(Some(Deprecation::new(
+                Some(Version::from_str("1.2.0").unwrap()),
+                Some("Carts are gone"),
+                None,
+            ))),
+        );
+        let render = |language| {
+            let files = CodeGenerator::of(&env, language).export().unwrap();
+            let item = files.iter().find(|file| {
+                let stem = file
+                    .path()
+                    .file_stem()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_lowercase();
+                stem == "item"
+            });
+            item.unwrap().content().to_string()
+        };
+
+        assert!(render(TargetLanguage::Kotlin).contains(
+            "@Deprecated(\"Deprecated since 1.2.0: Carts are gone.\")
+data class Item(
+    @Deprecated(\"Deprecated. Replaced by cart/Item.sku.\")
+    val code: kotlin.String,"
+        ));
+        assert!(render(TargetLanguage::Java).contains(
+            "/** @deprecated Deprecated since 1.2.0: Carts are gone. */
+@Deprecated(since = \"1.2.0\")
+public record Item(
+    @Deprecated
+    java.lang.String code,"
+        ));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::entity::epath::EPath;
 use crate::entity::event::Event;
+use crate::entity::Deprecation;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ComponentType {
     Module,
     Page,
@@ -9,6 +10,27 @@ pub enum ComponentType {
     Widget,
 }
 
+impl ComponentType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "module" => Some(ComponentType::Module),
+            "page" => Some(ComponentType::Page),
+            "element" => Some(ComponentType::Element),
+            "widget" => Some(ComponentType::Widget),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ComponentType::Module => "module",
+            ComponentType::Page => "page",
+            ComponentType::Element => "element",
+            ComponentType::Widget => "widget",
+        }
+    }
+}
+
 type ComponentEPath = EPath;
 
 #[derive(Debug)]
@@ -18,9 +40,26 @@ pub struct Component {
     description: String,
     events: Vec<Event>,
     children: Vec<ComponentEPath>,
+    deprecation: Option<Deprecation>,
 }
 
 impl Component {
+    pub fn new(
+        epath: EPath,
+        component_type: ComponentType,
+        events: Vec<Event>,
+        children: Vec<ComponentEPath>,
+    ) -> Self {
+        Component {
+            epath,
+            component_type,
+            description: "".to_string(),
+            events,
+            children,
+            deprecation: None,
+        }
+    }
+
     pub fn module(epath: EPath) -> Self {
         Component {
             epath,
@@ -28,6 +67,7 @@ impl Component {
             description: "".to_string(),
             events: vec![],
             children: vec![],
+            deprecation: None,
         }
     }
 
@@ -43,6 +83,11 @@ impl Component {
         &self.description
     }
 
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
     pub fn events(&self) -> &Vec<Event> {
         &self.events
     }
@@ -50,4 +95,13 @@ impl Component {
     pub fn children(&self) -> &Vec<ComponentEPath> {
         &self.children
     }
+
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        self.deprecation.as_ref()
+    }
+
+    pub fn with_deprecation(mut self, deprecation: Option<Deprecation>) -> Self {
+        self.deprecation = deprecation;
+        self
+    }
 }
@@ -0,0 +1,69 @@
+use crate::source::entity::Version;
+
+/// Resolved deprecation of a declaration, its replacement being fully qualified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deprecation {
+    since: Option<Version>,
+    reason: Option<String>,
+    replaced_by: Option<String>,
+}
+
+impl Deprecation {
+    pub fn new(since: Option<Version>, reason: Option<&str>, replaced_by: Option<String>) -> Self {
+        Deprecation {
+            since,
+            reason: reason.map(str::to_string),
+            replaced_by,
+        }
+    }
+
+    pub fn since(&self) -> Option<&Version> {
+        self.since.as_ref()
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    /// Replacement of the declaration, e.g. `tuna-bank:client-app/common/PhoneLoginPage/OnLogin`.
+    pub fn replaced_by(&self) -> Option<&str> {
+        self.replaced_by.as_deref()
+    }
+
+    /// Single sentence summary, e.g. `Deprecated since 1.2.0: Use email. Replaced by X.`.
+    pub fn message(&self) -> String {
+        let mut message = "Deprecated".to_string();
+        if let Some(since) = &self.since {
+            message.push_str(&format!(" since {}", since.to_string()));
+        }
+        if let Some(reason) = &self.reason {
+            message.push_str(&format!(": {}", reason.trim_end_matches('.')));
+        }
+        message.push('.');
+        if let Some(replaced_by) = &self.replaced_by {
+            message.push_str(&format!(" Replaced by {}.", replaced_by));
+        }
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_message() {
+        let deprecation = Deprecation::new(
+            Some(Version::from_str("1.2.0").unwrap()),
+            Some("Passcodes are sent by email now."),
+            Some("tuna-bank:client-app/common/OnEmailLogin".to_string()),
+        );
+
+        assert_eq!(
+            deprecation.message(),
+            "Deprecated since 1.2.0: Passcodes are sent by email now. Replaced by tuna-bank:client-app/common/OnEmailLogin."
+        );
+        assert_eq!(Deprecation::new(None, None, None).message(), "Deprecated.");
+    }
+}
@@ -1,9 +1,18 @@
-use super::{ProjectIdentifier, TypeDefinition};
-use crate::entity::component::Component;
+use super::{
+    CollectionTypeDefinition, EnumTypeDefinition, ObjectTypeDefinition, PrimitiveType,
+    ProjectIdentifier, TypeConstraints, TypeDefinition, TypeDefinitionIdentifier,
+};
+use crate::entity::component::{Component, ComponentType};
+use crate::entity::epath::EPath;
+use crate::entity::target_language::TargetLanguage;
+use crate::source::entity::Version;
+use std::collections::HashMap;
+use crate::entity::event::Event;
 
 #[derive(Debug)]
 pub struct Environment {
     identifier: ProjectIdentifier,
+    version: Version,
     type_definitions: Vec<TypeDefinition>,
     components: Vec<Component>,
 }
@@ -12,9 +21,11 @@ impl Environment {
     
     pub fn empty(
         identifier: ProjectIdentifier,
+        version: Version,
     ) -> Self {
         Environment {
             identifier,
+            version,
             type_definitions: vec![],
             components: vec![],
         }
@@ -27,4 +38,178 @@ impl Environment {
     pub fn push_component(&mut self, component: Component) {
         self.components.push(component);
     }
+
+    pub fn identifier(&self) -> &ProjectIdentifier {
+        &self.identifier
+    }
+
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    pub fn type_definitions(&self) -> &Vec<TypeDefinition> {
+        &self.type_definitions
+    }
+
+    pub fn components(&self) -> &Vec<Component> {
+        &self.components
+    }
+
+    pub fn modules(&self) -> impl Iterator<Item = &Component> {
+        self.components
+            .iter()
+            .filter(|component| *component.component_type() == ComponentType::Module)
+    }
+
+    pub fn component(&self, epath: &EPath) -> Option<&Component> {
+        self.components
+            .iter()
+            .find(|component| component.epath() == epath)
+    }
+
+    /// `root` followed by all of its descendants, depth first.
+    pub fn component_tree<'a>(&'a self, root: &'a Component) -> Vec<&'a Component> {
+        let mut buf = vec![root];
+        for child in root.children() {
+            if let Some(child) = self.component(child) {
+                buf.extend(self.component_tree(child));
+            }
+        }
+        buf
+    }
+
+    pub fn type_definition(&self, identifier: &TypeDefinitionIdentifier) -> Option<&TypeDefinition> {
+        self.type_definitions
+            .iter()
+            .find(|definition| definition.identifier() == identifier)
+    }
+
+    /// Every event of every component paired with the component that emits it.
+    pub fn events(&self) -> impl Iterator<Item = (&Component, &Event)> {
+        self.components
+            .iter()
+            .flat_map(|component| component.events().iter().map(move |event| (component, event)))
+    }
+
+    /// Event addressed by the path of its component extended with the event name, e.g.
+    /// `common/PhoneLoginPage/OnLogin`.
+    pub fn event(&self, path: &EPath) -> Option<(&Component, &Event)> {
+        let (name, component_path) = path.segments.split_last()?;
+        let component = self.component(&EPath::new(component_path.to_vec()))?;
+        let event = component.events().iter().find(|event| event.name() == name)?;
+        Some((component, event))
+    }
+
+    /// Type definitions starting at `identifier` and following aliases down to the terminal
+    /// definition. Stops early on unknown identifiers and alias cycles.
+    pub fn alias_chain(&self, identifier: &TypeDefinitionIdentifier) -> Vec<&TypeDefinition> {
+        let mut chain: Vec<&TypeDefinition> = Vec::new();
+        let mut current = self.type_definition(identifier);
+
+        while let Some(definition) = current {
+            if chain.iter().any(|seen| seen.identifier() == definition.identifier()) {
+                break;
+            }
+            chain.push(definition);
+            current = match definition {
+                TypeDefinition::Alias(alias) => self.type_definition(alias.aliases()),
+                TypeDefinition::NativeBinding(_)
+                | TypeDefinition::Enum(_)
+                | TypeDefinition::Object(_)
+                | TypeDefinition::Collection(_) => None,
+            };
+        }
+
+        chain
+    }
+
+    pub fn terminal_type_definition(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+    ) -> Option<&TypeDefinition> {
+        self.alias_chain(identifier).last().copied()
+    }
+
+    /// Patterns declared along the alias chain, all of which a value of the type has to match.
+    pub fn patterns(&self, identifier: &TypeDefinitionIdentifier) -> Vec<&str> {
+        self.alias_chain(identifier)
+            .into_iter()
+            .filter_map(|definition| definition.pattern())
+            .collect()
+    }
+
+    /// Example of the type, falling back to the closest example along the alias chain.
+    pub fn example(&self, identifier: &TypeDefinitionIdentifier) -> Option<&str> {
+        self.alias_chain(identifier)
+            .into_iter()
+            .find_map(|definition| definition.example())
+    }
+
+    /// Constraints of the type narrowed by every constraint inherited along the alias chain.
+    pub fn constraints(&self, identifier: &TypeDefinitionIdentifier) -> TypeConstraints {
+        self.alias_chain(identifier)
+            .into_iter()
+            .rev()
+            .fold(TypeConstraints::default(), |inherited, definition| {
+                definition.constraints().narrowed_by(&inherited)
+            })
+    }
+
+    /// Native bindings of the terminal definition of the type.
+    pub fn native_bindings(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+    ) -> Option<&HashMap<TargetLanguage, String>> {
+        match self.terminal_type_definition(identifier)? {
+            TypeDefinition::NativeBinding(definition) => Some(definition.bindings()),
+            _ => None,
+        }
+    }
+
+    /// Enum definition the type ends up at, if it is an enum or an alias of one.
+    pub fn enum_definition(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+    ) -> Option<&EnumTypeDefinition> {
+        match self.terminal_type_definition(identifier)? {
+            TypeDefinition::Enum(definition) => Some(definition),
+            _ => None,
+        }
+    }
+
+    /// Object definition the type ends up at, if it is an object or an alias of one.
+    pub fn object_definition(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+    ) -> Option<&ObjectTypeDefinition> {
+        match self.terminal_type_definition(identifier)? {
+            TypeDefinition::Object(definition) => Some(definition),
+            _ => None,
+        }
+    }
+
+    /// Collection definition the type ends up at, if it is a list, a map or an alias of one.
+    pub fn collection_definition(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+    ) -> Option<&CollectionTypeDefinition> {
+        match self.terminal_type_definition(identifier)? {
+            TypeDefinition::Collection(definition) => Some(definition),
+            _ => None,
+        }
+    }
+
+    /// Primitive type of the terminal definition, enums are represented by their underlying
+    /// type and strings by default.
+    pub fn primitive_type(&self, identifier: &TypeDefinitionIdentifier) -> Option<PrimitiveType> {
+        match self.terminal_type_definition(identifier)? {
+            TypeDefinition::Enum(definition) => match definition.underlying() {
+                Some(underlying) => self
+                    .terminal_type_definition(underlying)
+                    .and_then(|terminal| PrimitiveType::of(terminal.identifier())),
+                None => Some(PrimitiveType::String),
+            },
+            definition => PrimitiveType::of(definition.identifier()),
+        }
+    }
 }
@@ -1,16 +1,121 @@
-use std::fs::OpenOptions;
-use crate::entity::TypeDefinitionIdentifier;
+use serde_json::Value;
+
+use crate::entity::{Deprecation, TypeDefinitionIdentifier};
 
 #[derive(Debug)]
 pub struct EventArgument {
     name: String,
-    description: OpenOptions,
-    type_ref: TypeDefinitionIdentifier
+    description: String,
+    type_ref: TypeDefinitionIdentifier,
+    required: bool,
+    default: Option<Value>,
+    deprecation: Option<Deprecation>,
 }
 
 #[derive(Debug)]
 pub struct Event {
     name: String,
-    description: OpenOptions,
+    description: String,
     arguments: Vec<EventArgument>,
-}
\ No newline at end of file
+    deprecation: Option<Deprecation>,
+}
+
+impl EventArgument {
+    pub fn new(name: &str, type_ref: TypeDefinitionIdentifier) -> Self {
+        EventArgument {
+            name: name.to_string(),
+            description: "".to_string(),
+            type_ref,
+            required: true,
+            default: None,
+            deprecation: None,
+        }
+    }
+
+    /// Parameter that may be left out of payloads, taking `default` when there is one.
+    pub fn new_optional(
+        name: &str,
+        type_ref: TypeDefinitionIdentifier,
+        default: Option<Value>,
+    ) -> Self {
+        EventArgument {
+            name: name.to_string(),
+            description: "".to_string(),
+            type_ref,
+            required: false,
+            default,
+            deprecation: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    pub fn type_ref(&self) -> &TypeDefinitionIdentifier {
+        &self.type_ref
+    }
+
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    pub fn default(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        self.deprecation.as_ref()
+    }
+
+    pub fn with_deprecation(mut self, deprecation: Option<Deprecation>) -> Self {
+        self.deprecation = deprecation;
+        self
+    }
+}
+
+impl Event {
+    pub fn new(name: &str, arguments: Vec<EventArgument>) -> Self {
+        Event {
+            name: name.to_string(),
+            description: "".to_string(),
+            arguments,
+            deprecation: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = description.to_string();
+        self
+    }
+
+    pub fn arguments(&self) -> &Vec<EventArgument> {
+        &self.arguments
+    }
+
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        self.deprecation.as_ref()
+    }
+
+    pub fn with_deprecation(mut self, deprecation: Option<Deprecation>) -> Self {
+        self.deprecation = deprecation;
+        self
+    }
+}
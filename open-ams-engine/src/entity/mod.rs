@@ -5,7 +5,15 @@ pub mod epath;
 pub mod target_language;
 mod event;
 mod component;
+mod primitive_type;
+mod type_constraints;
+mod deprecation;
 
 pub use environment::*;
 pub use project_identifier::*;
-pub use type_definition::*;
\ No newline at end of file
+pub use type_definition::*;
+pub use event::*;
+pub use component::*;
+pub use primitive_type::*;
+pub use type_constraints::*;
+pub use deprecation::*;
@@ -0,0 +1,34 @@
+use crate::entity::{ProjectIdentifier, TypeDefinitionIdentifier};
+
+/// Format-independent value kind of a terminal stdlib type, used by exporters
+/// to map the alias chain onto their own primitive types. Dates, identifiers and codes
+/// are transported as strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PrimitiveType {
+    String,
+    Integer,
+    /// 64-bit integer.
+    Long,
+    Float,
+    Boolean,
+}
+
+impl PrimitiveType {
+    pub fn of(identifier: &TypeDefinitionIdentifier) -> Option<Self> {
+        if *identifier.project() != ProjectIdentifier::new("ams", "std")
+            || identifier.path().segments.len() != 1
+        {
+            return None;
+        }
+
+        match identifier.name() {
+            "String" | "Email" | "Uuid" | "Url" | "Date" | "DateTime" | "Duration"
+            | "CurrencyCode" | "CountryCode" | "LocaleTag" => Some(PrimitiveType::String),
+            "Integer" => Some(PrimitiveType::Integer),
+            "Long" => Some(PrimitiveType::Long),
+            "Float" => Some(PrimitiveType::Float),
+            "Boolean" => Some(PrimitiveType::Boolean),
+            _ => None,
+        }
+    }
+}
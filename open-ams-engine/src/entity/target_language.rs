@@ -1,4 +1,7 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, Hash, Clone)]
 pub enum TargetLanguage {
@@ -11,3 +14,60 @@ pub enum TargetLanguage {
     #[serde(rename = "dart")]
     Dart,
 }
+
+impl TargetLanguage {
+    pub const ALL: [TargetLanguage; 4] = [
+        TargetLanguage::Kotlin,
+        TargetLanguage::Java,
+        TargetLanguage::Swift,
+        TargetLanguage::Dart,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TargetLanguage::Kotlin => "Kotlin",
+            TargetLanguage::Java => "Java",
+            TargetLanguage::Swift => "Swift",
+            TargetLanguage::Dart => "Dart",
+        }
+    }
+
+    /// Whether `type_name` is a native string, number or boolean type of the language, the only
+    /// types whose values generated code can write as literals.
+    pub fn has_literals(&self, type_name: &str) -> bool {
+        let literal_types: &[&str] = match self {
+            TargetLanguage::Kotlin => &[
+                "kotlin.String",
+                "kotlin.Int",
+                "kotlin.Long",
+                "kotlin.Double",
+                "kotlin.Boolean",
+            ],
+            TargetLanguage::Java => &[
+                "java.lang.String",
+                "java.lang.Integer",
+                "java.lang.Long",
+                "java.lang.Double",
+                "java.lang.Boolean",
+            ],
+            TargetLanguage::Swift => &["String", "Int", "Int64", "Double", "Bool"],
+            TargetLanguage::Dart => &["String", "int", "double", "bool"],
+        };
+        literal_types.contains(&type_name)
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Unknown target language '{0}', expected one of: kotlin, java, swift, dart")]
+pub struct UnknownTargetLanguage(String);
+
+impl FromStr for TargetLanguage {
+    type Err = UnknownTargetLanguage;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TargetLanguage::ALL
+            .into_iter()
+            .find(|language| language.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| UnknownTargetLanguage(s.to_string()))
+    }
+}
@@ -0,0 +1,200 @@
+use serde::{Deserialize, Serialize};
+
+/// Well-known string formats, named after their JSON Schema `format` counterparts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StringFormat {
+    #[serde(rename = "email")]
+    Email,
+    #[serde(rename = "uuid")]
+    Uuid,
+    #[serde(rename = "uri")]
+    Uri,
+    #[serde(rename = "date")]
+    Date,
+    #[serde(rename = "date-time")]
+    DateTime,
+    #[serde(rename = "duration")]
+    Duration,
+}
+
+impl StringFormat {
+    pub const ALL: [StringFormat; 6] = [
+        StringFormat::Email,
+        StringFormat::Uuid,
+        StringFormat::Uri,
+        StringFormat::Date,
+        StringFormat::DateTime,
+        StringFormat::Duration,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StringFormat::Email => "email",
+            StringFormat::Uuid => "uuid",
+            StringFormat::Uri => "uri",
+            StringFormat::Date => "date",
+            StringFormat::DateTime => "date-time",
+            StringFormat::Duration => "duration",
+        }
+    }
+
+    /// Regular expression accepting values of the format. Deliberately lenient, it catches
+    /// obviously wrong values rather than implementing the full RFCs.
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            StringFormat::Email => r"^[^@\s]+@[^@\s]+\.[^@\s]+$",
+            StringFormat::Uuid => {
+                r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"
+            }
+            StringFormat::Uri => r"^[a-zA-Z][a-zA-Z0-9+.-]*:[^\s]+$",
+            StringFormat::Date => r"^[0-9]{4}-[0-9]{2}-[0-9]{2}$",
+            StringFormat::DateTime => {
+                r"^[0-9]{4}-[0-9]{2}-[0-9]{2}[Tt ][0-9]{2}:[0-9]{2}:[0-9]{2}(\.[0-9]+)?([Zz]|[+-][0-9]{2}:[0-9]{2})$"
+            }
+            // At least one component after `P` and after `T`, spelled out as alternations since
+            // the regex crate has no lookaheads.
+            StringFormat::Duration => concat!(
+                r"^P(([0-9]+Y([0-9]+M)?([0-9]+W)?([0-9]+D)?|[0-9]+M([0-9]+W)?([0-9]+D)?",
+                r"|[0-9]+W([0-9]+D)?|[0-9]+D)",
+                r"(T([0-9]+H([0-9]+M)?([0-9]+(\.[0-9]+)?S)?|[0-9]+M([0-9]+(\.[0-9]+)?S)?",
+                r"|[0-9]+(\.[0-9]+)?S))?",
+                r"|T([0-9]+H([0-9]+M)?([0-9]+(\.[0-9]+)?S)?|[0-9]+M([0-9]+(\.[0-9]+)?S)?",
+                r"|[0-9]+(\.[0-9]+)?S))$"
+            ),
+        }
+    }
+}
+
+/// Declarative value constraints of a type definition, in addition to its `pattern`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypeConstraints {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multiple_of: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<StringFormat>,
+}
+
+impl TypeConstraints {
+    pub fn is_empty(&self) -> bool {
+        *self == TypeConstraints::default()
+    }
+
+    /// Constraints of an alias narrowed by the constraints it inherits from the aliased type:
+    /// bounds are intersected, while `multipleOf` and `format` of the alias take precedence.
+    pub fn narrowed_by(&self, inherited: &TypeConstraints) -> TypeConstraints {
+        TypeConstraints {
+            min_length: max_of(self.min_length, inherited.min_length),
+            max_length: min_of(self.max_length, inherited.max_length),
+            minimum: max_of(self.minimum, inherited.minimum),
+            maximum: min_of(self.maximum, inherited.maximum),
+            multiple_of: self.multiple_of.or(inherited.multiple_of),
+            format: self.format.or(inherited.format),
+        }
+    }
+
+    /// Whether `value` satisfies `multipleOf`, tolerating floating point rounding.
+    pub fn is_multiple(&self, value: f64) -> bool {
+        match self.multiple_of {
+            Some(multiple_of) if multiple_of > 0.0 => {
+                let quotient = value / multiple_of;
+                (quotient - quotient.round()).abs() < 1e-9
+            }
+            _ => true,
+        }
+    }
+}
+
+fn max_of<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if a >= b { a } else { b }),
+        (a, b) => a.or(b),
+    }
+}
+
+fn min_of<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(if a <= b { a } else { b }),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize() {
+        let constraints: TypeConstraints =
+            serde_yaml::from_str("minimum: 0\nmaximum: 150\nmultipleOf: 1\nformat: date-time")
+                .unwrap();
+
+        assert_eq!(
+            constraints,
+            TypeConstraints {
+                minimum: Some(0.0),
+                maximum: Some(150.0),
+                multiple_of: Some(1.0),
+                format: Some(StringFormat::DateTime),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_narrowed_by() {
+        let alias = TypeConstraints {
+            minimum: Some(18.0),
+            max_length: Some(10),
+            ..Default::default()
+        };
+        let inherited = TypeConstraints {
+            minimum: Some(0.0),
+            maximum: Some(150.0),
+            max_length: Some(20),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            alias.narrowed_by(&inherited),
+            TypeConstraints {
+                minimum: Some(18.0),
+                maximum: Some(150.0),
+                max_length: Some(10),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_duration_pattern() {
+        let regex = regex::Regex::new(StringFormat::Duration.pattern()).unwrap();
+
+        for valid in ["P1Y2M3D", "P2W", "PT5M", "PT1H30M", "P1DT12H", "PT0.5S"] {
+            assert!(regex.is_match(valid), "{}", valid);
+        }
+        for invalid in ["P", "PT", "P1DT", "P1H", "PT1D", "P١D", "5M"] {
+            assert!(!regex.is_match(invalid), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_is_multiple() {
+        let constraints = TypeConstraints {
+            multiple_of: Some(0.1),
+            ..Default::default()
+        };
+
+        assert!(constraints.is_multiple(0.3));
+        assert!(!constraints.is_multiple(0.35));
+        assert!(TypeConstraints::default().is_multiple(0.35));
+    }
+}
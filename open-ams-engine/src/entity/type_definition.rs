@@ -1,9 +1,10 @@
 use crate::entity::epath::EPath;
-use crate::entity::ProjectIdentifier;
+use crate::entity::{Deprecation, ProjectIdentifier, TypeConstraints};
 use crate::source::entity::TargetLanguage;
+use serde_json::Value;
 use std::collections::HashMap;
-use std::fmt::{Debug, Formatter};
-use uuid::{uuid, Uuid};
+use std::fmt::{Debug, Display, Formatter};
+use uuid::Uuid;
 
 #[derive(Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct TypeDefinitionIdentifier {
@@ -23,6 +24,28 @@ impl TypeDefinitionIdentifier {
             EPath::new(vec![uuid]),
         )
     }
+
+    pub fn project(&self) -> &ProjectIdentifier {
+        &self.project
+    }
+
+    pub fn path(&self) -> &EPath {
+        &self.path
+    }
+
+    pub fn name(&self) -> &str {
+        self.path.segments.last().map(String::as_str).unwrap_or("")
+    }
+
+    /// Standard library identifier of a collection of `element`, e.g.
+    /// `ams:std/List<tuna-bank:client-app/common/PhoneNumber>`.
+    pub fn collection(kind: CollectionKind, element: &TypeDefinitionIdentifier) -> Self {
+        let name = match kind {
+            CollectionKind::List => format!("List<{}>", element),
+            CollectionKind::Map => format!("Map<String, {}>", element),
+        };
+        TypeDefinitionIdentifier::new(ProjectIdentifier::new("ams", "std"), EPath::new(vec![name]))
+    }
 }
 
 impl Debug for TypeDefinitionIdentifier {
@@ -31,42 +54,423 @@ impl Debug for TypeDefinitionIdentifier {
     }
 }
 
+impl Display for TypeDefinitionIdentifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{:?}", self.project, self.path)
+    }
+}
+
 #[derive(Debug)]
 pub struct NativeBindingTypeDefinition {
     identifier: TypeDefinitionIdentifier,
     bindings: HashMap<TargetLanguage, String>,
+    pattern: Option<String>,
+    example: Option<String>,
+    constraints: TypeConstraints,
+    description: Option<String>,
+    deprecation: Option<Deprecation>,
 }
 
 #[derive(Debug)]
 pub struct AliasTypeDefinition {
     identifier: TypeDefinitionIdentifier,
     aliases: TypeDefinitionIdentifier,
+    pattern: Option<String>,
+    example: Option<String>,
+    constraints: TypeConstraints,
+    description: Option<String>,
+    deprecation: Option<Deprecation>,
+}
+
+/// Closed list of values, represented by its underlying type or `ams:std/String` by default.
+#[derive(Debug)]
+pub struct EnumTypeDefinition {
+    identifier: TypeDefinitionIdentifier,
+    underlying: Option<TypeDefinitionIdentifier>,
+    values: Vec<EnumValue>,
+    pattern: Option<String>,
+    example: Option<String>,
+    constraints: TypeConstraints,
+    description: Option<String>,
+    deprecation: Option<Deprecation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumValue {
+    value: String,
+    description: Option<String>,
+}
+
+/// Composite type made of named fields, each of them referencing another type.
+#[derive(Debug)]
+pub struct ObjectTypeDefinition {
+    identifier: TypeDefinitionIdentifier,
+    fields: Vec<ObjectField>,
+    pattern: Option<String>,
+    example: Option<String>,
+    constraints: TypeConstraints,
+    description: Option<String>,
+    deprecation: Option<Deprecation>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectField {
+    name: String,
+    type_ref: TypeDefinitionIdentifier,
+    required: bool,
+    default: Option<Value>,
+    description: Option<String>,
+    deprecation: Option<Deprecation>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionKind {
+    List,
+    /// Map with string keys.
+    Map,
+}
+
+/// `List<T>` or `Map<String, T>`, created by the resolver for every collection type expression.
+#[derive(Debug)]
+pub struct CollectionTypeDefinition {
+    identifier: TypeDefinitionIdentifier,
+    kind: CollectionKind,
+    element: TypeDefinitionIdentifier,
+    constraints: TypeConstraints,
 }
 
 #[derive(Debug)]
 pub enum TypeDefinition {
     NativeBinding(NativeBindingTypeDefinition),
     Alias(AliasTypeDefinition),
+    Enum(EnumTypeDefinition),
+    Object(ObjectTypeDefinition),
+    Collection(CollectionTypeDefinition),
+}
+
+impl NativeBindingTypeDefinition {
+    pub fn bindings(&self) -> &HashMap<TargetLanguage, String> {
+        &self.bindings
+    }
+}
+
+impl AliasTypeDefinition {
+    pub fn aliases(&self) -> &TypeDefinitionIdentifier {
+        &self.aliases
+    }
+}
+
+impl EnumTypeDefinition {
+    pub fn identifier(&self) -> &TypeDefinitionIdentifier {
+        &self.identifier
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        self.deprecation.as_ref()
+    }
+
+    pub fn underlying(&self) -> Option<&TypeDefinitionIdentifier> {
+        self.underlying.as_ref()
+    }
+
+    pub fn values(&self) -> &[EnumValue] {
+        &self.values
+    }
+
+    pub fn contains(&self, value: &str) -> bool {
+        self.values.iter().any(|candidate| candidate.value == value)
+    }
+}
+
+impl EnumValue {
+    pub fn new(value: &str, description: Option<&str>) -> Self {
+        EnumValue {
+            value: value.to_string(),
+            description: description.map(str::to_string),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+impl ObjectTypeDefinition {
+    pub fn identifier(&self) -> &TypeDefinitionIdentifier {
+        &self.identifier
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        self.deprecation.as_ref()
+    }
+
+    pub fn fields(&self) -> &[ObjectField] {
+        &self.fields
+    }
+
+    pub fn field(&self, name: &str) -> Option<&ObjectField> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+}
+
+impl ObjectField {
+    pub fn new(name: &str, type_ref: TypeDefinitionIdentifier) -> Self {
+        ObjectField {
+            name: name.to_string(),
+            type_ref,
+            required: true,
+            default: None,
+            description: None,
+            deprecation: None,
+        }
+    }
+
+    /// Field that may be left out, taking `default` when there is one.
+    pub fn new_optional(
+        name: &str,
+        type_ref: TypeDefinitionIdentifier,
+        default: Option<Value>,
+    ) -> Self {
+        ObjectField {
+            name: name.to_string(),
+            type_ref,
+            required: false,
+            default,
+            description: None,
+            deprecation: None,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_ref(&self) -> &TypeDefinitionIdentifier {
+        &self.type_ref
+    }
+
+    pub fn required(&self) -> bool {
+        self.required
+    }
+
+    pub fn default(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn with_description(mut self, description: Option<&str>) -> Self {
+        self.description = description.map(str::to_string);
+        self
+    }
+
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        self.deprecation.as_ref()
+    }
+
+    pub fn with_deprecation(mut self, deprecation: Option<Deprecation>) -> Self {
+        self.deprecation = deprecation;
+        self
+    }
+}
+
+impl CollectionTypeDefinition {
+    pub fn identifier(&self) -> &TypeDefinitionIdentifier {
+        &self.identifier
+    }
+
+    pub fn kind(&self) -> CollectionKind {
+        self.kind
+    }
+
+    /// Type of the list elements or of the map values.
+    pub fn element(&self) -> &TypeDefinitionIdentifier {
+        &self.element
+    }
 }
 
 impl TypeDefinition {
     pub fn new_native_binding(
         identifier: TypeDefinitionIdentifier,
         bindings: &HashMap<TargetLanguage, String>,
+        pattern: Option<String>,
+        example: Option<String>,
+        constraints: TypeConstraints,
     ) -> Self {
         TypeDefinition::NativeBinding(NativeBindingTypeDefinition {
             identifier,
             bindings: bindings.clone(),
+            pattern,
+            example,
+            constraints,
+            description: None,
+            deprecation: None,
         })
     }
 
     pub fn new_alias(
         identifier: TypeDefinitionIdentifier,
         aliases: TypeDefinitionIdentifier,
+        pattern: Option<String>,
+        example: Option<String>,
+        constraints: TypeConstraints,
     ) -> Self {
         TypeDefinition::Alias(AliasTypeDefinition {
             identifier,
             aliases,
+            pattern,
+            example,
+            constraints,
+            description: None,
+            deprecation: None,
+        })
+    }
+
+    pub fn new_enum(
+        identifier: TypeDefinitionIdentifier,
+        underlying: Option<TypeDefinitionIdentifier>,
+        values: Vec<EnumValue>,
+        pattern: Option<String>,
+        example: Option<String>,
+        constraints: TypeConstraints,
+    ) -> Self {
+        TypeDefinition::Enum(EnumTypeDefinition {
+            identifier,
+            underlying,
+            values,
+            pattern,
+            example,
+            constraints,
+            description: None,
+            deprecation: None,
+        })
+    }
+
+    pub fn new_object(
+        identifier: TypeDefinitionIdentifier,
+        fields: Vec<ObjectField>,
+        pattern: Option<String>,
+        example: Option<String>,
+        constraints: TypeConstraints,
+    ) -> Self {
+        TypeDefinition::Object(ObjectTypeDefinition {
+            identifier,
+            fields,
+            pattern,
+            example,
+            constraints,
+            description: None,
+            deprecation: None,
+        })
+    }
+
+    pub fn new_collection(kind: CollectionKind, element: TypeDefinitionIdentifier) -> Self {
+        TypeDefinition::Collection(CollectionTypeDefinition {
+            identifier: TypeDefinitionIdentifier::collection(kind, &element),
+            kind,
+            element,
+            constraints: TypeConstraints::default(),
         })
     }
+
+    pub fn with_description(mut self, description: Option<&str>) -> Self {
+        let description = description.map(str::to_string);
+        match &mut self {
+            TypeDefinition::NativeBinding(definition) => definition.description = description,
+            TypeDefinition::Alias(definition) => definition.description = description,
+            TypeDefinition::Enum(definition) => definition.description = description,
+            TypeDefinition::Object(definition) => definition.description = description,
+            TypeDefinition::Collection(_) => {}
+        }
+        self
+    }
+
+    pub fn with_deprecation(mut self, deprecation: Option<Deprecation>) -> Self {
+        match &mut self {
+            TypeDefinition::NativeBinding(definition) => definition.deprecation = deprecation,
+            TypeDefinition::Alias(definition) => definition.deprecation = deprecation,
+            TypeDefinition::Enum(definition) => definition.deprecation = deprecation,
+            TypeDefinition::Object(definition) => definition.deprecation = deprecation,
+            TypeDefinition::Collection(_) => {}
+        }
+        self
+    }
+
+    pub fn identifier(&self) -> &TypeDefinitionIdentifier {
+        match self {
+            TypeDefinition::NativeBinding(definition) => &definition.identifier,
+            TypeDefinition::Alias(definition) => &definition.identifier,
+            TypeDefinition::Enum(definition) => &definition.identifier,
+            TypeDefinition::Object(definition) => &definition.identifier,
+            TypeDefinition::Collection(definition) => &definition.identifier,
+        }
+    }
+
+    pub fn pattern(&self) -> Option<&str> {
+        match self {
+            TypeDefinition::NativeBinding(definition) => definition.pattern.as_deref(),
+            TypeDefinition::Alias(definition) => definition.pattern.as_deref(),
+            TypeDefinition::Enum(definition) => definition.pattern.as_deref(),
+            TypeDefinition::Object(definition) => definition.pattern.as_deref(),
+            TypeDefinition::Collection(_) => None,
+        }
+    }
+
+    /// Markdown description of the type, collections don't have one.
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            TypeDefinition::NativeBinding(definition) => definition.description.as_deref(),
+            TypeDefinition::Alias(definition) => definition.description.as_deref(),
+            TypeDefinition::Enum(definition) => definition.description.as_deref(),
+            TypeDefinition::Object(definition) => definition.description.as_deref(),
+            TypeDefinition::Collection(_) => None,
+        }
+    }
+
+    /// Deprecation of the type, collections are never deprecated on their own.
+    pub fn deprecation(&self) -> Option<&Deprecation> {
+        match self {
+            TypeDefinition::NativeBinding(definition) => definition.deprecation.as_ref(),
+            TypeDefinition::Alias(definition) => definition.deprecation.as_ref(),
+            TypeDefinition::Enum(definition) => definition.deprecation.as_ref(),
+            TypeDefinition::Object(definition) => definition.deprecation.as_ref(),
+            TypeDefinition::Collection(_) => None,
+        }
+    }
+
+    pub fn example(&self) -> Option<&str> {
+        match self {
+            TypeDefinition::NativeBinding(definition) => definition.example.as_deref(),
+            TypeDefinition::Alias(definition) => definition.example.as_deref(),
+            TypeDefinition::Enum(definition) => definition.example.as_deref(),
+            TypeDefinition::Object(definition) => definition.example.as_deref(),
+            TypeDefinition::Collection(_) => None,
+        }
+    }
+
+    /// Constraints declared by this definition itself, see
+    /// [`Environment::constraints`](crate::entity::Environment::constraints) for the inherited ones.
+    pub fn constraints(&self) -> &TypeConstraints {
+        match self {
+            TypeDefinition::NativeBinding(definition) => &definition.constraints,
+            TypeDefinition::Alias(definition) => &definition.constraints,
+            TypeDefinition::Enum(definition) => &definition.constraints,
+            TypeDefinition::Object(definition) => &definition.constraints,
+            TypeDefinition::Collection(definition) => &definition.constraints,
+        }
+    }
 }
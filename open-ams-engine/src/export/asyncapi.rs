@@ -0,0 +1,197 @@
+use serde_json::{json, Map, Value};
+
+use crate::entity::{Component, ComponentType, Environment, Event};
+use crate::export::naming::sanitize_identifier;
+use crate::export::{ExportError, ExportedFile, JsonSchemaExporter};
+
+pub const ASYNCAPI_VERSION: &str = "3.0.0";
+
+/// Exports the whole tracking plan as a single AsyncAPI 3.0 document.
+///
+/// Every page component becomes a channel carrying the events of the page and of its widgets and
+/// elements; events declared outside of any page land in a channel of their module.
+#[derive(Debug)]
+pub struct AsyncApiExporter<'env> {
+    env: &'env Environment,
+}
+
+impl<'env> AsyncApiExporter<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        AsyncApiExporter { env }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        Ok(vec![ExportedFile::new(
+            "asyncapi.yaml",
+            serde_yaml::to_string(&self.document())?,
+        )])
+    }
+
+    pub fn document(&self) -> Value {
+        let schemas = JsonSchemaExporter::of(self.env);
+        let mut channels = Map::new();
+        let mut operations = Map::new();
+        let mut messages = Map::new();
+
+        for (owner, events) in self.channels() {
+            let channel_id = sanitize_identifier(&owner.epath().segments.join("_"));
+            let mut channel_messages = Map::new();
+            let mut operation_messages = Vec::new();
+
+            for (component, event) in events {
+                let message_id = message_id(component, event);
+                messages.insert(
+                    message_id.clone(),
+                    json!({
+                        "name": event.name(),
+                        "title": format!("{}/{}", component.epath().to_string(), event.name()),
+                        "payload": schemas.event_schema_at(
+                            event,
+                            &format!("#/components/messages/{}/payload", message_id),
+                        ),
+                    }),
+                );
+                channel_messages.insert(
+                    message_id.clone(),
+                    json!({ "$ref": format!("#/components/messages/{}", message_id) }),
+                );
+                operation_messages.push(json!({
+                    "$ref": format!("#/channels/{}/messages/{}", channel_id, message_id)
+                }));
+            }
+
+            operations.insert(
+                format!("send_{}", channel_id),
+                json!({
+                    "action": "send",
+                    "channel": { "$ref": format!("#/channels/{}", channel_id) },
+                    "messages": operation_messages,
+                }),
+            );
+            channels.insert(
+                channel_id,
+                json!({
+                    "address": owner.epath().to_string(),
+                    "messages": channel_messages,
+                }),
+            );
+        }
+
+        json!({
+            "asyncapi": ASYNCAPI_VERSION,
+            "info": {
+                "title": self.env.identifier().to_string(),
+                "version": self.env.version().to_string(),
+            },
+            "channels": channels,
+            "operations": operations,
+            "components": { "messages": messages },
+        })
+    }
+
+    /// Events grouped by the page (or module, outside of pages) that owns them.
+    fn channels(&self) -> Vec<(&'env Component, Vec<(&'env Component, &'env Event)>)> {
+        let mut buf = Vec::new();
+        for module in self.env.modules() {
+            self.collect_channel_events(module, module, &mut buf);
+        }
+        buf.retain(|(_, events)| !events.is_empty());
+        buf
+    }
+
+    fn collect_channel_events(
+        &self,
+        owner: &'env Component,
+        component: &'env Component,
+        buf: &mut Vec<(&'env Component, Vec<(&'env Component, &'env Event)>)>,
+    ) {
+        let owner = match component.component_type() {
+            ComponentType::Page => component,
+            _ => owner,
+        };
+        if !buf
+            .iter()
+            .any(|(existing, _)| existing.epath() == owner.epath())
+        {
+            buf.push((owner, Vec::new()));
+        }
+        let (_, events) = buf
+            .iter_mut()
+            .find(|(existing, _)| existing.epath() == owner.epath())
+            .unwrap();
+        events.extend(component.events().iter().map(|event| (component, event)));
+
+        for child in component.children() {
+            if let Some(child) = self.env.component(child) {
+                self.collect_channel_events(owner, child, buf);
+            }
+        }
+    }
+}
+
+fn message_id(component: &Component, event: &Event) -> String {
+    let segments: Vec<&str> = component
+        .epath()
+        .segments
+        .iter()
+        .map(String::as_str)
+        .chain([event.name()])
+        .collect();
+    sanitize_identifier(&segments.join("_"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::example_environment;
+
+    #[test]
+    fn test_info() {
+        let env = example_environment();
+        let document = AsyncApiExporter::of(&env).document();
+
+        assert_eq!(document["asyncapi"], "3.0.0");
+        assert_eq!(
+            document["info"],
+            json!({ "title": "tuna-bank:client-app", "version": "0.1.0" })
+        );
+    }
+
+    #[test]
+    fn test_channels_per_page() {
+        let env = example_environment();
+        let document = AsyncApiExporter::of(&env).document();
+
+        let channels = document["channels"].as_object().unwrap();
+        assert_eq!(
+            channels.keys().collect::<Vec<_>>(),
+            vec!["common", "common_PhoneLoginPage"]
+        );
+        assert_eq!(
+            channels["common_PhoneLoginPage"]["messages"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec![
+                "common_PhoneLoginPage_LogingButton_OnClick",
+                "common_PhoneLoginPage_OnLogin"
+            ]
+        );
+        assert_eq!(
+            document["operations"]["send_common_PhoneLoginPage"]["channel"]["$ref"],
+            "#/channels/common_PhoneLoginPage"
+        );
+    }
+
+    #[test]
+    fn test_message_payload() {
+        let env = example_environment();
+        let document = AsyncApiExporter::of(&env).document();
+        let payload =
+            &document["components"]["messages"]["common_PhoneLoginPage_OnLogin"]["payload"];
+
+        assert_eq!(payload["type"], "object");
+        assert_eq!(payload["properties"]["phone"]["type"], "string");
+    }
+}
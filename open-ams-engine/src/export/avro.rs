@@ -0,0 +1,336 @@
+use std::collections::HashSet;
+
+use serde_json::{json, Value};
+
+use crate::entity::{
+    CollectionKind, Component, Environment, Event, PrimitiveType, TypeDefinitionIdentifier,
+};
+use crate::export::naming::sanitize_identifier;
+use crate::export::{ExportError, ExportedFile};
+
+/// Exports every resolved event as an Avro record schema (`.avsc`).
+#[derive(Debug)]
+pub struct AvroExporter<'env> {
+    env: &'env Environment,
+}
+
+impl<'env> AvroExporter<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        AvroExporter { env }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let mut buf = Vec::new();
+        for (component, event) in self.env.events() {
+            buf.push(ExportedFile::new(
+                ExportedFile::event_path(component, event, "avsc"),
+                serde_json::to_string_pretty(&self.event_schema(component, event)?)?,
+            ));
+        }
+        Ok(buf)
+    }
+
+    pub fn event_schema(&self, component: &Component, event: &Event) -> Result<Value, ExportError> {
+        let mut records = HashSet::new();
+        let mut fields = Vec::new();
+        for argument in event.arguments() {
+            let type_schema = self.type_schema(argument.type_ref(), &mut records)?;
+            fields.push(with_doc(
+                field(
+                    argument.name(),
+                    type_schema,
+                    argument.required(),
+                    argument.default(),
+                ),
+                argument.description(),
+            ));
+        }
+
+        Ok(with_doc(
+            json!({
+                "type": "record",
+                "name": sanitize_identifier(event.name()),
+                "namespace": self.namespace(component),
+                "fields": fields,
+            }),
+            event.description(),
+        ))
+    }
+
+    /// Namespace made of the project group and name followed by the component `EPath`,
+    /// e.g. `tuna_bank.client_app.common.PhoneLoginPage`.
+    pub fn namespace(&self, component: &Component) -> String {
+        let identifier = self.env.identifier();
+        [identifier.group(), identifier.name()]
+            .into_iter()
+            .chain(component.epath().segments.iter().map(String::as_str))
+            .map(sanitize_identifier)
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+
+    /// Avro `array` for lists, `map` for maps, a named `record` for objects and a primitive type
+    /// name for anything else.
+    ///
+    /// A record is defined where it is first used and referenced by its full name afterwards, as
+    /// Avro allows a named type to be defined only once per schema. `records` holds the full names
+    /// defined so far.
+    fn type_schema(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+        records: &mut HashSet<String>,
+    ) -> Result<Value, ExportError> {
+        if let Some(collection) = self.env.collection_definition(identifier) {
+            let element = self.type_schema(collection.element(), records)?;
+            return Ok(match collection.kind() {
+                CollectionKind::List => json!({ "type": "array", "items": element }),
+                CollectionKind::Map => json!({ "type": "map", "values": element }),
+            });
+        }
+        if let Some(object_definition) = self.env.object_definition(identifier) {
+            let full_name = record_full_name(identifier);
+            if !records.insert(full_name.clone()) {
+                return Ok(json!(full_name));
+            }
+            let mut fields = Vec::new();
+            for object_field in object_definition.fields() {
+                let type_schema = self.type_schema(object_field.type_ref(), records)?;
+                fields.push(with_doc(
+                    field(
+                        object_field.name(),
+                        type_schema,
+                        object_field.required(),
+                        object_field.default(),
+                    ),
+                    object_field.description().unwrap_or_default(),
+                ));
+            }
+            return Ok(with_doc(
+                json!({ "type": "record", "name": full_name, "fields": fields }),
+                object_definition.description().unwrap_or_default(),
+            ));
+        }
+        match self.env.primitive_type(identifier) {
+            Some(primitive_type) => Ok(json!(avro_type(primitive_type))),
+            None => Err(ExportError::UnsupportedType {
+                type_ref: identifier.clone(),
+                format: "Avro",
+            }),
+        }
+    }
+}
+
+/// Optional fields are a union with `null`, listed first unless the field has a default, as
+/// Avro requires defaults to match the first type of a union.
+fn field(name: &str, type_schema: Value, required: bool, default: Option<&Value>) -> Value {
+    let name = sanitize_identifier(name);
+    match (required, default) {
+        (true, _) => json!({ "name": name, "type": type_schema }),
+        (false, None) => json!({
+            "name": name,
+            "type": ["null", type_schema],
+            "default": null,
+        }),
+        (false, Some(default)) => json!({
+            "name": name,
+            "type": [type_schema, "null"],
+            "default": default,
+        }),
+    }
+}
+
+/// Full name of the record of an object type, made of its project group and name followed by
+/// its path, e.g. `tuna_bank.client_app.cart.CartItem`.
+fn record_full_name(identifier: &TypeDefinitionIdentifier) -> String {
+    let project = identifier.project();
+    [project.group(), project.name()]
+        .into_iter()
+        .chain(identifier.path().segments.iter().map(String::as_str))
+        .map(sanitize_identifier)
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// `schema` with its `doc` attribute set, unchanged without a description.
+fn with_doc(mut schema: Value, description: &str) -> Value {
+    if let (Value::Object(map), false) = (&mut schema, description.is_empty()) {
+        map.insert("doc".to_string(), json!(description));
+    }
+    schema
+}
+
+fn avro_type(primitive_type: PrimitiveType) -> &'static str {
+    match primitive_type {
+        PrimitiveType::String => "string",
+        PrimitiveType::Integer => "int",
+        PrimitiveType::Long => "long",
+        PrimitiveType::Float => "double",
+        PrimitiveType::Boolean => "boolean",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EventArgument, ObjectField, TypeConstraints, TypeDefinition};
+    use crate::util::{example_environment, std_type, type_identifier};
+
+    #[test]
+    fn test_event_schema() {
+        let env = example_environment();
+        let (component, event) = env.events().find(|(_, e)| e.name() == "OnLogin").unwrap();
+        let schema = AvroExporter::of(&env)
+            .event_schema(component, event)
+            .unwrap();
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "record",
+                "name": "OnLogin",
+                "namespace": "tuna_bank.client_app.common.PhoneLoginPage",
+                "fields": [
+                    { "name": "passcode", "type": "string" },
+                    { "name": "phone", "type": "string" },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_collection_types() {
+        let mut env = example_environment();
+        let integer = std_type("Integer");
+        let list = TypeDefinitionIdentifier::collection(CollectionKind::List, &integer);
+        let map = TypeDefinitionIdentifier::collection(CollectionKind::Map, &list);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            integer,
+        ));
+        env.push_type_definition(TypeDefinition::new_collection(CollectionKind::Map, list));
+
+        assert_eq!(
+            AvroExporter::of(&env)
+                .type_schema(&map, &mut HashSet::new())
+                .unwrap(),
+            json!({
+                "type": "map",
+                "values": { "type": "array", "items": "int" },
+            })
+        );
+    }
+
+    #[test]
+    fn test_object_records() {
+        let mut env = example_environment();
+        let string = std_type("String");
+        let node = type_identifier("tuna-bank:client-app", "catalog/Node");
+        let children = TypeDefinitionIdentifier::collection(CollectionKind::List, &node);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            node.clone(),
+        ));
+        env.push_type_definition(TypeDefinition::new_object(
+            node.clone(),
+            vec![
+                ObjectField::new("name", string),
+                ObjectField::new("children", children),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let event = Event::new(
+            "OnMove",
+            vec![
+                EventArgument::new("from", node.clone()),
+                EventArgument::new("to", node),
+            ],
+        );
+        let schema = AvroExporter::of(&env)
+            .event_schema(&env.components()[0], &event)
+            .unwrap();
+
+        assert_eq!(
+            schema["fields"],
+            json!([
+                {
+                    "name": "from",
+                    "type": {
+                        "type": "record",
+                        "name": "tuna_bank.client_app.catalog.Node",
+                        "fields": [
+                            { "name": "name", "type": "string" },
+                            {
+                                "name": "children",
+                                "type": { "type": "array", "items": "tuna_bank.client_app.catalog.Node" },
+                            },
+                        ],
+                    },
+                },
+                { "name": "to", "type": "tuna_bank.client_app.catalog.Node" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_unsupported_type() {
+        let env = example_environment();
+        let event = Event::new(
+            "OnScan",
+            vec![EventArgument::new(
+                "code",
+                TypeDefinitionIdentifier::undefined(),
+            )],
+        );
+
+        assert!(matches!(
+            AvroExporter::of(&env).event_schema(&env.components()[0], &event),
+            Err(ExportError::UnsupportedType { format: "Avro", .. })
+        ));
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let env = example_environment();
+        let string = std_type("String");
+        let event = Event::new(
+            "OnSearch",
+            vec![
+                EventArgument::new_optional("referrer", string.clone(), None),
+                EventArgument::new_optional("source", string, Some(json!("home"))),
+            ],
+        );
+        let schema = AvroExporter::of(&env)
+            .event_schema(&env.components()[0], &event)
+            .unwrap();
+
+        assert_eq!(
+            schema["fields"],
+            json!([
+                { "name": "referrer", "type": ["null", "string"], "default": null },
+                { "name": "source", "type": ["string", "null"], "default": "home" },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_doc() {
+        let env = example_environment();
+        let string = std_type("String");
+        let event = Event::new(
+            "OnSearch",
+            vec![EventArgument::new("query", string).with_description("Text typed by the user")],
+        )
+        .with_description("User searched the catalog");
+        let schema = AvroExporter::of(&env)
+            .event_schema(&env.components()[0], &event)
+            .unwrap();
+
+        assert_eq!(schema["doc"], json!("User searched the catalog"));
+        assert_eq!(
+            schema["fields"],
+            json!([{ "name": "query", "type": "string", "doc": "Text typed by the user" }])
+        );
+    }
+}
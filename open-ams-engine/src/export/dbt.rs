@@ -0,0 +1,395 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+
+use crate::entity::{Component, Environment, Event, EventArgument, PrimitiveType};
+use crate::export::ddl::{event_tables, parameter_columns};
+use crate::export::naming::{event_table_name, snake_case};
+use crate::export::{ExportError, ExportedFile, EVENT_TIME_COLUMN, USER_ID_COLUMN};
+
+#[derive(Debug, Serialize)]
+struct DbtSources {
+    version: u32,
+    sources: Vec<DbtSource>,
+}
+
+#[derive(Debug, Serialize)]
+struct DbtSource {
+    name: String,
+    description: String,
+    tables: Vec<DbtTable>,
+}
+
+#[derive(Debug, Serialize)]
+struct DbtTable {
+    name: String,
+    description: String,
+    columns: Vec<DbtColumn>,
+}
+
+#[derive(Debug, Serialize)]
+struct DbtColumn {
+    name: String,
+    description: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tests: Vec<DbtTest>,
+}
+
+#[derive(Debug)]
+enum DbtTest {
+    NotNull,
+    AcceptedValues { values: Vec<String>, quote: bool },
+}
+
+#[derive(Debug, Serialize)]
+struct DbtAcceptedValues<'a> {
+    values: &'a [String],
+    quote: bool,
+}
+
+/// Generic tests are written as `not_null` or as a single-key map holding the test arguments,
+/// e.g. `accepted_values: { values: [...] }`.
+impl Serialize for DbtTest {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            DbtTest::NotNull => serializer.serialize_str("not_null"),
+            DbtTest::AcceptedValues { values, quote } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(
+                    "accepted_values",
+                    &DbtAcceptedValues {
+                        values,
+                        quote: *quote,
+                    },
+                )?;
+                map.end()
+            }
+        }
+    }
+}
+
+/// Exports dbt source definitions for the event tables produced by
+/// [`DdlExporter`](crate::export::DdlExporter) together with a staging model per event.
+#[derive(Debug)]
+pub struct DbtExporter<'env> {
+    env: &'env Environment,
+}
+
+impl<'env> DbtExporter<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        DbtExporter { env }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let source = self.source_name();
+        let directory: PathBuf = ["models", "staging", source.as_str()].iter().collect();
+
+        let mut buf = vec![ExportedFile::new(
+            directory.join(format!("_{}__sources.yml", source)),
+            serde_yaml::to_string(&self.sources()?)?,
+        )];
+        for (table, component, event) in event_tables(self.env)? {
+            buf.push(ExportedFile::new(
+                directory.join(format!("stg_{}__{}.sql", source, table)),
+                self.staging_model(component, event, &table)?,
+            ));
+        }
+        Ok(buf)
+    }
+
+    /// dbt source named after the project, e.g. `client_app`.
+    pub fn source_name(&self) -> String {
+        snake_case(self.env.identifier().name())
+    }
+
+    fn sources(&self) -> Result<DbtSources, ExportError> {
+        let tables = event_tables(self.env)?
+            .into_iter()
+            .map(|(_, component, event)| self.table(component, event))
+            .collect::<Result<Vec<DbtTable>, ExportError>>()?;
+
+        Ok(DbtSources {
+            version: 2,
+            sources: vec![DbtSource {
+                name: self.source_name(),
+                description: format!("Events tracked by {}", self.env.identifier()),
+                tables,
+            }],
+        })
+    }
+
+    fn table(&self, component: &Component, event: &Event) -> Result<DbtTable, ExportError> {
+        let mut columns = vec![
+            DbtColumn {
+                name: EVENT_TIME_COLUMN.to_string(),
+                description: "Time at which the event was emitted".to_string(),
+                tests: vec![DbtTest::NotNull],
+            },
+            DbtColumn {
+                name: USER_ID_COLUMN.to_string(),
+                description: "Identifier of the user that emitted the event".to_string(),
+                tests: vec![],
+            },
+        ];
+        columns.extend(
+            parameter_columns(component, event)?
+                .into_iter()
+                .map(|(name, argument)| self.column(name, argument)),
+        );
+
+        Ok(DbtTable {
+            name: event_table_name(component, event),
+            description: match event.description().trim() {
+                "" => format!(
+                    "{} emitted by {}",
+                    event.name(),
+                    component.epath().to_string()
+                ),
+                declared => declared.to_string(),
+            },
+            columns,
+        })
+    }
+
+    fn column(&self, name: String, argument: &EventArgument) -> DbtColumn {
+        let type_ref = argument.type_ref();
+        let description = match argument.description().trim() {
+            "" => {
+                let mut synthetic =
+                    format!("`{}` parameter of type `{}`", argument.name(), type_ref);
+                for pattern in self.env.patterns(type_ref) {
+                    write!(synthetic, ", matching `{}`", pattern).unwrap();
+                }
+                synthetic
+            }
+            declared => declared.to_string(),
+        };
+
+        let mut tests = Vec::new();
+        if argument.required() {
+            tests.push(DbtTest::NotNull);
+        }
+        if let Some(enum_definition) = self.env.enum_definition(type_ref) {
+            tests.push(DbtTest::AcceptedValues {
+                values: enum_definition
+                    .values()
+                    .iter()
+                    .map(|value| value.value().to_string())
+                    .collect(),
+                quote: self.env.primitive_type(type_ref) == Some(PrimitiveType::String),
+            });
+        }
+
+        DbtColumn {
+            name,
+            description,
+            tests,
+        }
+    }
+
+    fn staging_model(
+        &self,
+        component: &Component,
+        event: &Event,
+        table: &str,
+    ) -> Result<String, ExportError> {
+        let mut columns = vec![EVENT_TIME_COLUMN.to_string(), USER_ID_COLUMN.to_string()];
+        columns.extend(
+            parameter_columns(component, event)?
+                .into_iter()
+                .map(|(name, _)| name),
+        );
+
+        let mut out = String::new();
+        writeln!(out, "with source as (").unwrap();
+        writeln!(
+            out,
+            "    select * from {{{{ source('{}', '{}') }}}}",
+            self.source_name(),
+            table
+        )
+        .unwrap();
+        writeln!(out, "),\n").unwrap();
+        writeln!(out, "renamed as (").unwrap();
+        writeln!(out, "    select").unwrap();
+        writeln!(out, "        {}", columns.join(",\n        ")).unwrap();
+        writeln!(out, "    from source").unwrap();
+        writeln!(out, ")\n").unwrap();
+        writeln!(out, "select * from renamed").unwrap();
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{ComponentType, EnumValue, TypeConstraints, TypeDefinition};
+    use crate::source::entity::EPath;
+    use crate::util::{example_environment, std_type, type_identifier};
+    use std::path::Path;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_files() {
+        let env = example_environment();
+        let files = DbtExporter::of(&env).export().unwrap();
+        let paths: Vec<&Path> = files.iter().map(|file| file.path()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("models/staging/client_app/_client_app__sources.yml"),
+                Path::new("models/staging/client_app/stg_client_app__common_user_logged_in.sql"),
+                Path::new(
+                    "models/staging/client_app/stg_client_app__common_phone_login_page_on_login.sql"
+                ),
+                Path::new(
+                    "models/staging/client_app/stg_client_app__common_phone_login_page_loging_button_on_click.sql"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sources() {
+        let env = example_environment();
+        let files = DbtExporter::of(&env).export().unwrap();
+        let sources: serde_yaml::Value = serde_yaml::from_str(files[0].content()).unwrap();
+        let table = &sources["sources"][0]["tables"][1];
+
+        assert_eq!(sources["sources"][0]["name"], "client_app");
+        assert_eq!(table["name"], "common_phone_login_page_on_login");
+        assert_eq!(table["columns"][3]["name"], "phone");
+        assert_eq!(
+            table["columns"][3]["description"],
+            "`phone` parameter of type `tuna-bank:client-app/common/PhoneNumber`, matching `^\\+?[0-9]{1,3}-?[0-9]{1,14}$`"
+        );
+        assert_eq!(table["columns"][3]["tests"][0], "not_null");
+        assert!(table["columns"][1].get("tests").is_none());
+    }
+
+    #[test]
+    fn test_declared_descriptions() {
+        let mut env = example_environment();
+        env.push_component(Component::new(
+            EPath::from_str("search").unwrap(),
+            ComponentType::Module,
+            vec![Event::new(
+                "OnSearch",
+                vec![EventArgument::new("query", std_type("String"))
+                    .with_description("Text typed by the user\n")],
+            )
+            .with_description("Search submitted from the toolbar\n")],
+            vec![],
+        ));
+        let (component, event) = env
+            .events()
+            .find(|(_, event)| event.name() == "OnSearch")
+            .unwrap();
+        let table = DbtExporter::of(&env).table(component, event).unwrap();
+
+        assert_eq!(table.description, "Search submitted from the toolbar");
+        assert_eq!(table.columns[2].description, "Text typed by the user");
+    }
+
+    #[test]
+    fn test_column_collision() {
+        let mut env = example_environment();
+        env.push_component(Component::new(
+            EPath::from_str("share").unwrap(),
+            ComponentType::Module,
+            vec![Event::new(
+                "OnShare",
+                vec![EventArgument::new("eventTime", std_type("String"))],
+            )],
+            vec![],
+        ));
+
+        assert!(matches!(
+            DbtExporter::of(&env).export(),
+            Err(ExportError::ColumnCollision { table, column })
+                if table == "share_on_share" && column == EVENT_TIME_COLUMN
+        ));
+    }
+
+    #[test]
+    fn test_table_collision() {
+        let mut env = example_environment();
+        env.push_component(Component::new(
+            EPath::from_str("share").unwrap(),
+            ComponentType::Module,
+            vec![
+                Event::new("OnShare", vec![]),
+                Event::new("on_share", vec![]),
+            ],
+            vec![],
+        ));
+
+        assert!(matches!(
+            DbtExporter::of(&env).export(),
+            Err(ExportError::TableCollision { table, .. }) if table == "share_on_share"
+        ));
+    }
+
+    #[test]
+    fn test_accepted_values() {
+        let mut env = example_environment();
+        let identifier = type_identifier("tuna-bank:client-app", "common/LoginMethod");
+        env.push_type_definition(TypeDefinition::new_enum(
+            identifier.clone(),
+            None,
+            vec![
+                EnumValue::new("sms", None),
+                EnumValue::new("password", None),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let column = DbtExporter::of(&env).column(
+            "method".to_string(),
+            &EventArgument::new("method", identifier),
+        );
+
+        assert_eq!(
+            serde_yaml::to_string(&column.tests).unwrap(),
+            "- not_null
+- accepted_values:
+    values:
+    - sms
+    - password
+    quote: true
+"
+        );
+    }
+
+    #[test]
+    fn test_staging_model() {
+        let env = example_environment();
+        let files = DbtExporter::of(&env).export().unwrap();
+
+        assert_eq!(
+            files[2].content(),
+            "with source as (
+    select * from {{ source('client_app', 'common_phone_login_page_on_login') }}
+),
+
+renamed as (
+    select
+        event_time,
+        user_id,
+        passcode,
+        phone
+    from source
+)
+
+select * from renamed
+"
+        );
+    }
+}
@@ -0,0 +1,524 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::entity::{Component, Environment, Event, EventArgument, PrimitiveType};
+use crate::export::naming::{event_table_name, snake_case};
+use crate::export::{ExportError, ExportedFile};
+
+pub const EVENT_TIME_COLUMN: &str = "event_time";
+pub const USER_ID_COLUMN: &str = "user_id";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    Postgres,
+    BigQuery,
+    ClickHouse,
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Unknown SQL dialect '{0}', expected one of: postgres, bigquery, clickhouse")]
+pub struct UnknownSqlDialect(String);
+
+impl FromStr for SqlDialect {
+    type Err = UnknownSqlDialect;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "postgres" | "postgresql" => Ok(SqlDialect::Postgres),
+            "bigquery" => Ok(SqlDialect::BigQuery),
+            "clickhouse" => Ok(SqlDialect::ClickHouse),
+            _ => Err(UnknownSqlDialect(s.to_string())),
+        }
+    }
+}
+
+impl SqlDialect {
+    fn column_type(&self, primitive_type: Option<PrimitiveType>) -> &'static str {
+        match (self, primitive_type) {
+            (SqlDialect::Postgres, Some(PrimitiveType::Integer)) => "INTEGER",
+            (SqlDialect::Postgres, Some(PrimitiveType::Long)) => "BIGINT",
+            (SqlDialect::Postgres, Some(PrimitiveType::Float)) => "DOUBLE PRECISION",
+            (SqlDialect::Postgres, Some(PrimitiveType::Boolean)) => "BOOLEAN",
+            (SqlDialect::Postgres, _) => "TEXT",
+            (SqlDialect::BigQuery, Some(PrimitiveType::Integer | PrimitiveType::Long)) => "INT64",
+            (SqlDialect::BigQuery, Some(PrimitiveType::Float)) => "FLOAT64",
+            (SqlDialect::BigQuery, Some(PrimitiveType::Boolean)) => "BOOL",
+            (SqlDialect::BigQuery, _) => "STRING",
+            (SqlDialect::ClickHouse, Some(PrimitiveType::Integer)) => "Int32",
+            (SqlDialect::ClickHouse, Some(PrimitiveType::Long)) => "Int64",
+            (SqlDialect::ClickHouse, Some(PrimitiveType::Float)) => "Float64",
+            (SqlDialect::ClickHouse, Some(PrimitiveType::Boolean)) => "Bool",
+            (SqlDialect::ClickHouse, _) => "String",
+        }
+    }
+
+    /// Column type of object, list and map parameters, stored as JSON.
+    fn json_type(&self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "JSONB",
+            SqlDialect::BigQuery => "JSON",
+            SqlDialect::ClickHouse => "String",
+        }
+    }
+
+    fn timestamp_type(&self) -> &'static str {
+        match self {
+            SqlDialect::Postgres => "TIMESTAMPTZ",
+            SqlDialect::BigQuery => "TIMESTAMP",
+            SqlDialect::ClickHouse => "DateTime64(3)",
+        }
+    }
+
+    /// Quoted identifier, in double quotes on Postgres and ClickHouse and in backticks on
+    /// BigQuery.
+    fn identifier(&self, name: &str) -> String {
+        match self {
+            SqlDialect::Postgres | SqlDialect::ClickHouse => {
+                format!("\"{}\"", name.replace('"', "\"\""))
+            }
+            SqlDialect::BigQuery => format!("`{}`", name.replace('`', "\\`")),
+        }
+    }
+
+    fn column(&self, name: &str, column_type: &str, nullable: bool) -> String {
+        let name = self.identifier(name);
+        match (self, nullable) {
+            (SqlDialect::ClickHouse, true) => format!("{} Nullable({})", name, column_type),
+            (SqlDialect::ClickHouse, false) => format!("{} {}", name, column_type),
+            (_, true) => format!("{} {}", name, column_type),
+            (_, false) => format!("{} {} NOT NULL", name, column_type),
+        }
+    }
+
+    fn string_literal(&self, value: &str) -> String {
+        match self {
+            SqlDialect::Postgres => format!("'{}'", value.replace('\'', "''")),
+            SqlDialect::BigQuery | SqlDialect::ClickHouse => {
+                format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+            }
+        }
+    }
+}
+
+/// Exports a `CREATE TABLE` statement per resolved event.
+///
+/// Every table starts with the envelope columns `event_time` and `user_id`, followed by a column
+/// per event parameter, objects being stored as JSON. Enum parameters are restricted to their
+/// values with a `CHECK` constraint on Postgres and ClickHouse, BigQuery doesn't support check
+/// constraints. Parameters whose column would clash with an envelope column or with the column of
+/// another parameter, or tables that would clash with the table of another event, fail the export.
+/// Identifiers are quoted.
+#[derive(Debug)]
+pub struct DdlExporter<'env> {
+    env: &'env Environment,
+    dialect: SqlDialect,
+}
+
+impl<'env> DdlExporter<'env> {
+    pub fn of(env: &'env Environment, dialect: SqlDialect) -> Self {
+        DdlExporter { env, dialect }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let statements = event_tables(self.env)?
+            .into_iter()
+            .map(|(_, component, event)| self.create_table(component, event))
+            .collect::<Result<Vec<String>, ExportError>>()?;
+        Ok(vec![ExportedFile::new("events.sql", statements.join("\n"))])
+    }
+
+    pub fn create_table(
+        &self,
+        component: &Component,
+        event: &Event,
+    ) -> Result<String, ExportError> {
+        let dialect = self.dialect;
+        let table = dialect.identifier(&event_table_name(component, event));
+        let comment = format!("Emitted by {}", component.epath().to_string());
+        let parameter_columns = parameter_columns(component, event)?;
+
+        let mut columns = vec![
+            dialect.column(EVENT_TIME_COLUMN, dialect.timestamp_type(), false),
+            dialect.column(
+                USER_ID_COLUMN,
+                dialect.column_type(Some(PrimitiveType::String)),
+                true,
+            ),
+        ];
+        columns.extend(
+            parameter_columns
+                .iter()
+                .map(|(column, argument)| self.argument_column(column, argument)),
+        );
+        if dialect == SqlDialect::ClickHouse {
+            columns.extend(parameter_columns.iter().filter_map(|(column, argument)| {
+                let check = self.enum_check(column, argument)?;
+                let constraint = dialect.identifier(&format!("{}_values", column));
+                Some(format!("CONSTRAINT {} CHECK {}", constraint, check))
+            }));
+        }
+
+        let mut out = String::new();
+        writeln!(out, "CREATE TABLE {} (", table).unwrap();
+        writeln!(out, "    {}", columns.join(",\n    ")).unwrap();
+        match dialect {
+            SqlDialect::Postgres => {
+                writeln!(out, ");").unwrap();
+                writeln!(
+                    out,
+                    "COMMENT ON TABLE {} IS {};",
+                    table,
+                    dialect.string_literal(&comment)
+                )
+                .unwrap();
+            }
+            SqlDialect::BigQuery => {
+                writeln!(out, ")").unwrap();
+                writeln!(
+                    out,
+                    "PARTITION BY DATE({})",
+                    dialect.identifier(EVENT_TIME_COLUMN)
+                )
+                .unwrap();
+                writeln!(
+                    out,
+                    "OPTIONS (description = {});",
+                    dialect.string_literal(&comment)
+                )
+                .unwrap();
+            }
+            SqlDialect::ClickHouse => {
+                writeln!(out, ")").unwrap();
+                writeln!(out, "ENGINE = MergeTree").unwrap();
+                writeln!(out, "ORDER BY {}", dialect.identifier(EVENT_TIME_COLUMN)).unwrap();
+                writeln!(out, "COMMENT {};", dialect.string_literal(&comment)).unwrap();
+            }
+        }
+        Ok(out)
+    }
+
+    fn argument_column(&self, name: &str, argument: &EventArgument) -> String {
+        let type_ref = argument.type_ref();
+        let structured = self.env.object_definition(type_ref).is_some()
+            || self.env.collection_definition(type_ref).is_some();
+        let column_type = match structured {
+            true => self.dialect.json_type(),
+            false => self.dialect.column_type(self.env.primitive_type(type_ref)),
+        };
+        let column = self.dialect.column(name, column_type, !argument.required());
+        match (self.dialect, self.enum_check(name, argument)) {
+            (SqlDialect::Postgres, Some(check)) => format!("{} CHECK ({})", column, check),
+            _ => column,
+        }
+    }
+
+    /// `column IN (...)` restricting an enum parameter to its values.
+    fn enum_check(&self, column: &str, argument: &EventArgument) -> Option<String> {
+        let type_ref = argument.type_ref();
+        let enum_definition = self.env.enum_definition(type_ref)?;
+        let numeric = matches!(
+            self.env.primitive_type(type_ref),
+            Some(PrimitiveType::Integer | PrimitiveType::Long | PrimitiveType::Float)
+        );
+        let values: Vec<String> = enum_definition
+            .values()
+            .iter()
+            .map(|value| match numeric {
+                true => value.value().to_string(),
+                false => self.dialect.string_literal(value.value()),
+            })
+            .collect();
+        Some(format!(
+            "{} IN ({})",
+            self.dialect.identifier(column),
+            values.join(", ")
+        ))
+    }
+}
+
+/// Table of each event of `env`, failing when two events would share a table, e.g. `OnLogin`
+/// with `on_login` in the same component.
+pub(crate) fn event_tables(
+    env: &Environment,
+) -> Result<Vec<(String, &Component, &Event)>, ExportError> {
+    let mut owners: HashMap<String, String> = HashMap::new();
+    let mut buf = Vec::new();
+    for (component, event) in env.events() {
+        let table = event_table_name(component, event);
+        let owner = format!("{}/{}", component.epath().to_string(), event.name());
+        if let Some(first) = owners.insert(table.clone(), owner.clone()) {
+            return Err(ExportError::TableCollision {
+                table,
+                first,
+                second: owner,
+            });
+        }
+        buf.push((table, component, event));
+    }
+    Ok(buf)
+}
+
+/// Column of each parameter of `event`, failing when one would share its name with an envelope
+/// column or with another parameter, e.g. `userId` with `user_id`.
+pub(crate) fn parameter_columns<'e>(
+    component: &Component,
+    event: &'e Event,
+) -> Result<Vec<(String, &'e EventArgument)>, ExportError> {
+    let mut taken = vec![EVENT_TIME_COLUMN.to_string(), USER_ID_COLUMN.to_string()];
+    let mut buf = Vec::new();
+    for argument in event.arguments() {
+        let column = snake_case(argument.name());
+        if taken.contains(&column) {
+            return Err(ExportError::ColumnCollision {
+                table: event_table_name(component, event),
+                column,
+            });
+        }
+        taken.push(column.clone());
+        buf.push((column, argument));
+    }
+    Ok(buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{
+        CollectionKind, ComponentType, EnumValue, TypeConstraints, TypeDefinition,
+        TypeDefinitionIdentifier,
+    };
+    use crate::source::entity::EPath;
+    use crate::util::{example_environment, std_type, type_identifier};
+
+    fn on_login_table(dialect: SqlDialect) -> String {
+        let env = example_environment();
+        let (component, event) = env.events().find(|(_, e)| e.name() == "OnLogin").unwrap();
+        DdlExporter::of(&env, dialect)
+            .create_table(component, event)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_postgres() {
+        assert_eq!(
+            on_login_table(SqlDialect::Postgres),
+            "CREATE TABLE \"common_phone_login_page_on_login\" (
+    \"event_time\" TIMESTAMPTZ NOT NULL,
+    \"user_id\" TEXT,
+    \"passcode\" TEXT NOT NULL,
+    \"phone\" TEXT NOT NULL
+);
+COMMENT ON TABLE \"common_phone_login_page_on_login\" IS 'Emitted by common/PhoneLoginPage';
+"
+        );
+    }
+
+    #[test]
+    fn test_bigquery() {
+        assert_eq!(
+            on_login_table(SqlDialect::BigQuery),
+            "CREATE TABLE `common_phone_login_page_on_login` (
+    `event_time` TIMESTAMP NOT NULL,
+    `user_id` STRING,
+    `passcode` STRING NOT NULL,
+    `phone` STRING NOT NULL
+)
+PARTITION BY DATE(`event_time`)
+OPTIONS (description = 'Emitted by common/PhoneLoginPage');
+"
+        );
+    }
+
+    #[test]
+    fn test_clickhouse() {
+        assert_eq!(
+            on_login_table(SqlDialect::ClickHouse),
+            "CREATE TABLE \"common_phone_login_page_on_login\" (
+    \"event_time\" DateTime64(3),
+    \"user_id\" Nullable(String),
+    \"passcode\" String,
+    \"phone\" String
+)
+ENGINE = MergeTree
+ORDER BY \"event_time\"
+COMMENT 'Emitted by common/PhoneLoginPage';
+"
+        );
+    }
+
+    #[test]
+    fn test_enum_check() {
+        let mut env = example_environment();
+        let identifier = type_identifier("tuna-bank:client-app", "common/LoginMethod");
+        env.push_type_definition(TypeDefinition::new_enum(
+            identifier.clone(),
+            None,
+            vec![
+                EnumValue::new("sms", None),
+                EnumValue::new("pass'word", None),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let event = Event::new(
+            "OnLogin",
+            vec![EventArgument::new("loginMethod", identifier)],
+        );
+        let component = &env.components()[0];
+
+        assert_eq!(
+            DdlExporter::of(&env, SqlDialect::Postgres)
+                .create_table(component, &event)
+                .unwrap()
+                .lines()
+                .nth(3)
+                .unwrap(),
+            "    \"login_method\" TEXT NOT NULL CHECK (\"login_method\" IN ('sms', 'pass''word'))"
+        );
+        assert_eq!(
+            DdlExporter::of(&env, SqlDialect::ClickHouse)
+                .create_table(component, &event)
+                .unwrap()
+                .lines()
+                .nth(4)
+                .unwrap(),
+            "    CONSTRAINT \"login_method_values\" CHECK \"login_method\" IN ('sms', 'pass\\'word')"
+        );
+    }
+
+    #[test]
+    fn test_long_enum_check() {
+        let mut env = example_environment();
+        let identifier = type_identifier("tuna-bank:client-app", "common/AccountTier");
+        env.push_type_definition(TypeDefinition::new_enum(
+            identifier.clone(),
+            Some(std_type("Long")),
+            vec![EnumValue::new("1", None), EnumValue::new("2", None)],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let event = Event::new("OnUpgrade", vec![EventArgument::new("tier", identifier)]);
+        let table = DdlExporter::of(&env, SqlDialect::Postgres)
+            .create_table(&env.components()[0], &event)
+            .unwrap();
+
+        assert_eq!(
+            table.lines().nth(3),
+            Some("    \"tier\" BIGINT NOT NULL CHECK (\"tier\" IN (1, 2))")
+        );
+    }
+
+    #[test]
+    fn test_object_column() {
+        let mut env = example_environment();
+        let identifier = type_identifier("tuna-bank:client-app", "cart/CartItem");
+        env.push_type_definition(TypeDefinition::new_object(
+            identifier.clone(),
+            vec![],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let event = Event::new("OnAdd", vec![EventArgument::new("item", identifier)]);
+        let component = &env.components()[0];
+        let column = |dialect| {
+            DdlExporter::of(&env, dialect)
+                .create_table(component, &event)
+                .unwrap()
+                .lines()
+                .nth(3)
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(column(SqlDialect::Postgres), "    \"item\" JSONB NOT NULL");
+        assert_eq!(column(SqlDialect::BigQuery), "    `item` JSON NOT NULL");
+        assert_eq!(column(SqlDialect::ClickHouse), "    \"item\" String");
+    }
+
+    #[test]
+    fn test_collection_column() {
+        let mut env = example_environment();
+        let element = std_type("String");
+        let identifier = TypeDefinitionIdentifier::collection(CollectionKind::List, &element);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            element,
+        ));
+        let event = Event::new("OnTag", vec![EventArgument::new("tags", identifier)]);
+        let table = DdlExporter::of(&env, SqlDialect::Postgres)
+            .create_table(&env.components()[0], &event)
+            .unwrap();
+
+        assert_eq!(table.lines().nth(3), Some("    \"tags\" JSONB NOT NULL"));
+    }
+
+    #[test]
+    fn test_optional_column() {
+        let env = example_environment();
+        let identifier = std_type("String");
+        let event = Event::new(
+            "OnSearch",
+            vec![EventArgument::new_optional("referrer", identifier, None)],
+        );
+        let column = |dialect| {
+            DdlExporter::of(&env, dialect)
+                .create_table(&env.components()[0], &event)
+                .unwrap()
+                .lines()
+                .nth(3)
+                .unwrap()
+                .to_string()
+        };
+
+        assert_eq!(column(SqlDialect::Postgres), "    \"referrer\" TEXT");
+        assert_eq!(
+            column(SqlDialect::ClickHouse),
+            "    \"referrer\" Nullable(String)"
+        );
+    }
+
+    #[test]
+    fn test_column_collision() {
+        let env = example_environment();
+        let string = std_type("String");
+        let event = Event::new("OnShare", vec![EventArgument::new("userId", string)]);
+
+        assert!(matches!(
+            DdlExporter::of(&env, SqlDialect::Postgres).create_table(&env.components()[0], &event),
+            Err(ExportError::ColumnCollision { column, .. }) if column == USER_ID_COLUMN
+        ));
+    }
+
+    #[test]
+    fn test_table_collision() {
+        let mut env = example_environment();
+        for path in ["a_b/C", "a/b_c"] {
+            env.push_component(Component::new(
+                EPath::from_str(path).unwrap(),
+                ComponentType::Module,
+                vec![Event::new("OnOpen", vec![])],
+                vec![],
+            ));
+        }
+
+        assert!(matches!(
+            DdlExporter::of(&env, SqlDialect::Postgres).export(),
+            Err(ExportError::TableCollision { table, first, second })
+                if table == "a_b_c_on_open" && first == "a_b/C/OnOpen" && second == "a/b_c/OnOpen"
+        ));
+    }
+
+    #[test]
+    fn test_dialect_from_str() {
+        assert_eq!("Postgres".parse(), Ok(SqlDialect::Postgres));
+        assert_eq!("bigquery".parse(), Ok(SqlDialect::BigQuery));
+        assert_eq!("clickhouse".parse(), Ok(SqlDialect::ClickHouse));
+        assert!("mysql".parse::<SqlDialect>().is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use std::fmt::Write;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::entity::{Component, ComponentType, Environment, Event};
+use crate::export::naming::escape_identifier;
+use crate::export::{ExportError, ExportedFile};
+use crate::source::entity::EPath;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramFormat {
+    Mermaid,
+    Dot,
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Unknown diagram format '{0}', expected one of: mermaid, dot")]
+pub struct UnknownDiagramFormat(String);
+
+impl FromStr for DiagramFormat {
+    type Err = UnknownDiagramFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mermaid" => Ok(DiagramFormat::Mermaid),
+            "dot" | "graphviz" => Ok(DiagramFormat::Dot),
+            _ => Err(UnknownDiagramFormat(s.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DiagramError {
+    #[error("Component {0:?} not found")]
+    ComponentNotFound(EPath),
+}
+
+/// Renders the component tree (module → page → widgets → events) as a Mermaid flowchart or a
+/// Graphviz DOT digraph, giving every component type and events a distinct node shape.
+#[derive(Debug)]
+pub struct DiagramExporter<'env> {
+    env: &'env Environment,
+    format: DiagramFormat,
+}
+
+impl<'env> DiagramExporter<'env> {
+    pub fn of(env: &'env Environment, format: DiagramFormat) -> Self {
+        DiagramExporter { env, format }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let extension = match self.format {
+            DiagramFormat::Mermaid => "mmd",
+            DiagramFormat::Dot => "dot",
+        };
+        Ok(vec![ExportedFile::new(
+            format!("components.{}", extension),
+            self.render_project(),
+        )])
+    }
+
+    /// Diagram of every module of the project.
+    pub fn render_project(&self) -> String {
+        self.render(self.env.modules().collect())
+    }
+
+    /// Diagram of the tree below the component at `epath`.
+    pub fn render_component(&self, epath: &EPath) -> Result<String, DiagramError> {
+        let component = self
+            .env
+            .component(epath)
+            .ok_or_else(|| DiagramError::ComponentNotFound(epath.clone()))?;
+        Ok(self.render(vec![component]))
+    }
+
+    fn render(&self, roots: Vec<&Component>) -> String {
+        let mut nodes = String::new();
+        let mut edges = String::new();
+        for root in roots {
+            for component in self.env.component_tree(root) {
+                self.write_component(&mut nodes, &mut edges, component);
+            }
+        }
+
+        match self.format {
+            DiagramFormat::Mermaid => format!("flowchart TD\n{}{}", nodes, edges),
+            DiagramFormat::Dot => format!("digraph components {{\n{}{}}}\n", nodes, edges),
+        }
+    }
+
+    fn write_component(&self, nodes: &mut String, edges: &mut String, component: &Component) {
+        let id = component_id(component.epath());
+        let label = match component.epath().segments.last() {
+            Some(name) => name.as_str(),
+            None => "/",
+        };
+        self.write_node(
+            nodes,
+            &id,
+            label,
+            NodeKind::Component(component.component_type()),
+        );
+
+        for child in component.children() {
+            self.write_edge(edges, &id, &component_id(child));
+        }
+        for event in component.events() {
+            let event_id = event_id(component, event);
+            self.write_node(nodes, &event_id, event.name(), NodeKind::Event);
+            self.write_edge(edges, &id, &event_id);
+        }
+    }
+
+    fn write_node(&self, out: &mut String, id: &str, label: &str, kind: NodeKind) {
+        let label = label.replace('"', "'");
+        match self.format {
+            DiagramFormat::Mermaid => {
+                let (open, close) = match kind {
+                    NodeKind::Component(ComponentType::Module) => ("[/", "/]"),
+                    NodeKind::Component(ComponentType::Page) => ("[[", "]]"),
+                    NodeKind::Component(ComponentType::Widget) => ("(", ")"),
+                    NodeKind::Component(ComponentType::Element) => ("[", "]"),
+                    NodeKind::Event => (">", "]"),
+                };
+                writeln!(out, "    {}{}\"{}\"{}", id, open, label, close).unwrap();
+            }
+            DiagramFormat::Dot => {
+                let shape = match kind {
+                    NodeKind::Component(ComponentType::Module) => "folder",
+                    NodeKind::Component(ComponentType::Page) => "tab",
+                    NodeKind::Component(ComponentType::Widget) => "box, style=rounded",
+                    NodeKind::Component(ComponentType::Element) => "box",
+                    NodeKind::Event => "cds",
+                };
+                writeln!(out, "    {} [label=\"{}\", shape={}];", id, label, shape).unwrap();
+            }
+        }
+    }
+
+    fn write_edge(&self, out: &mut String, from: &str, to: &str) {
+        match self.format {
+            DiagramFormat::Mermaid => writeln!(out, "    {} --> {}", from, to).unwrap(),
+            DiagramFormat::Dot => writeln!(out, "    {} -> {};", from, to).unwrap(),
+        }
+    }
+}
+
+enum NodeKind<'a> {
+    Component(&'a ComponentType),
+    Event,
+}
+
+fn component_id(epath: &EPath) -> String {
+    node_id(
+        ["c"]
+            .into_iter()
+            .chain(epath.segments.iter().map(String::as_str)),
+    )
+}
+
+fn event_id(component: &Component, event: &Event) -> String {
+    node_id(
+        ["e"]
+            .into_iter()
+            .chain(component.epath().segments.iter().map(String::as_str))
+            .chain([event.name()]),
+    )
+}
+
+/// `parts` escaped with [`escape_identifier`] and joined with `__`. Escaped parts only contain
+/// `_` followed by two hex digits, so different parts always yield different ids.
+fn node_id<'a, I: IntoIterator<Item = &'a str>>(parts: I) -> String {
+    parts
+        .into_iter()
+        .map(escape_identifier)
+        .collect::<Vec<String>>()
+        .join("__")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::example_environment;
+
+    #[test]
+    fn test_mermaid_component() {
+        let env = example_environment();
+        let diagram = DiagramExporter::of(&env, DiagramFormat::Mermaid)
+            .render_component(&EPath::from_str("common/PhoneLoginPage").unwrap())
+            .unwrap();
+
+        assert_eq!(
+            diagram,
+            "flowchart TD
+    c__common__PhoneLoginPage[[\"PhoneLoginPage\"]]
+    e__common__PhoneLoginPage__OnLogin>\"OnLogin\"]
+    c__common__PhoneLoginPage__LogingButton(\"LogingButton\")
+    e__common__PhoneLoginPage__LogingButton__OnClick>\"OnClick\"]
+    c__common__PhoneLoginPage --> c__common__PhoneLoginPage__LogingButton
+    c__common__PhoneLoginPage --> e__common__PhoneLoginPage__OnLogin
+    c__common__PhoneLoginPage__LogingButton --> e__common__PhoneLoginPage__LogingButton__OnClick
+"
+        );
+    }
+
+    #[test]
+    fn test_dot_project() {
+        let env = example_environment();
+        let diagram = DiagramExporter::of(&env, DiagramFormat::Dot).render_project();
+
+        assert!(diagram.starts_with("digraph components {\n"));
+        assert!(diagram.contains("    c__common [label=\"common\", shape=folder];\n"));
+        assert!(diagram
+            .contains("    c__common__PhoneLoginPage [label=\"PhoneLoginPage\", shape=tab];\n"));
+        assert!(diagram.contains("    c__common -> c__common__PhoneLoginPage;\n"));
+        assert!(diagram.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_distinct_ids() {
+        let component = |path: &str| {
+            Component::new(
+                EPath::from_str(path).unwrap(),
+                ComponentType::Module,
+                vec![],
+                vec![],
+            )
+        };
+        let event = Event::new("OnOpen", vec![]);
+
+        assert_ne!(
+            component_id(&EPath::from_str("a_b/c").unwrap()),
+            component_id(&EPath::from_str("a/b_c").unwrap())
+        );
+        assert_ne!(
+            component_id(&EPath::from_str("a-b").unwrap()),
+            component_id(&EPath::from_str("a_b").unwrap())
+        );
+        assert_ne!(
+            event_id(&component("a_b"), &event),
+            event_id(&component("a/b"), &event)
+        );
+        assert_eq!(
+            event_id(&component("page-1"), &event),
+            "e__page_2d1__OnOpen"
+        );
+    }
+
+    #[test]
+    fn test_unknown_component() {
+        let env = example_environment();
+        let result = DiagramExporter::of(&env, DiagramFormat::Dot)
+            .render_component(&EPath::from_str("common/Missing").unwrap());
+
+        assert!(matches!(result, Err(DiagramError::ComponentNotFound(_))));
+    }
+}
@@ -0,0 +1,76 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+use crate::entity::{Component, Event, TypeDefinitionIdentifier};
+
+#[derive(Debug, PartialEq)]
+pub struct ExportedFile {
+    path: PathBuf,
+    content: String,
+}
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("Failed to serialize export: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Failed to read or write YAML: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+
+    #[error("Failed to write CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Failed to write export: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Column {column} of table {table} is taken by another column, rename the parameter")]
+    ColumnCollision { table: String, column: String },
+
+    #[error("Events {first} and {second} share table {table}, rename one of them")]
+    TableCollision {
+        table: String,
+        first: String,
+        second: String,
+    },
+
+    #[error("Type {type_ref} has no {format} representation")]
+    UnsupportedType {
+        type_ref: TypeDefinitionIdentifier,
+        format: &'static str,
+    },
+}
+
+impl ExportedFile {
+    pub fn new<P: Into<PathBuf>>(path: P, content: String) -> Self {
+        ExportedFile {
+            path: path.into(),
+            content,
+        }
+    }
+
+    /// Path named after `event`, placed in the directory mirroring the component `EPath`.
+    pub fn event_path(component: &Component, event: &Event, extension: &str) -> PathBuf {
+        let mut path: PathBuf = component.epath().segments.iter().collect();
+        path.push(format!("{}.{}", event.name(), extension));
+        path
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn write_to<P: AsRef<Path>>(&self, directory: P) -> Result<(), ExportError> {
+        let target = directory.as_ref().join(&self.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, &self.content)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,730 @@
+use std::fmt::Write;
+
+use serde::Serialize;
+
+use crate::entity::target_language::TargetLanguage;
+use crate::entity::{
+    Component, Deprecation, Environment, Event, EventArgument, TypeDefinition,
+    TypeDefinitionIdentifier,
+};
+use crate::export::naming::escape_identifier;
+use crate::export::{ExportError, ExportedFile};
+use crate::Assets;
+
+#[derive(Debug, Serialize)]
+struct SearchEntry {
+    title: String,
+    kind: &'static str,
+    url: String,
+    text: String,
+}
+
+/// Exports a self-contained static HTML site: module pages following the component hierarchy,
+/// a page per type and a client-side search over both. All pages live in a single directory so
+/// the site can be opened straight from disk.
+#[derive(Debug)]
+pub struct HtmlSiteExporter<'env> {
+    env: &'env Environment,
+}
+
+impl<'env> HtmlSiteExporter<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        HtmlSiteExporter { env }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let mut buf = vec![
+            ExportedFile::new("index.html", self.index()),
+            ExportedFile::new("style.css", asset("html/style.css")),
+            ExportedFile::new("search.js", asset("html/search.js")),
+            ExportedFile::new(
+                "search-index.js",
+                format!(
+                    "window.AMS_SEARCH_INDEX = {};\n",
+                    serde_json::to_string(&self.search_index())?
+                ),
+            ),
+        ];
+        for module in self.env.modules() {
+            buf.push(ExportedFile::new(
+                module_url(module),
+                self.module_page(module),
+            ));
+        }
+        for definition in self.env.type_definitions() {
+            buf.push(ExportedFile::new(
+                type_url(definition.identifier()),
+                self.type_page(definition),
+            ));
+        }
+        Ok(buf)
+    }
+
+    fn index(&self) -> String {
+        let mut body = String::new();
+        writeln!(
+            body,
+            "<h1>{}</h1>",
+            escape(&self.env.identifier().to_string())
+        )
+        .unwrap();
+        writeln!(
+            body,
+            "<p>Version <code>{}</code></p>",
+            escape(&self.env.version().to_string())
+        )
+        .unwrap();
+        writeln!(body, "<h2>Modules</h2>\n<ul>").unwrap();
+        for module in self.env.modules() {
+            writeln!(
+                body,
+                "<li><a href=\"{}\">{}</a></li>",
+                module_url(module),
+                escape(&module_title(module))
+            )
+            .unwrap();
+        }
+        writeln!(body, "</ul>\n<h2>Types</h2>\n<ul>").unwrap();
+        for definition in self.env.type_definitions() {
+            writeln!(body, "<li>{}</li>", type_link(definition.identifier())).unwrap();
+        }
+        writeln!(body, "</ul>").unwrap();
+        self.page(&self.env.identifier().to_string(), &body)
+    }
+
+    fn module_page(&self, module: &Component) -> String {
+        let mut body = String::new();
+        writeln!(
+            body,
+            "<h1>Module <code>{}</code></h1>",
+            escape(&module_title(module))
+        )
+        .unwrap();
+        write_description(&mut body, module.description());
+        for component in self.env.component_tree(module) {
+            if component.epath() != module.epath() {
+                writeln!(
+                    body,
+                    "<h2 id=\"{}\">{} <code>{}</code></h2>",
+                    component_anchor(component),
+                    escape(component.component_type().name()),
+                    escape(&component.epath().to_string())
+                )
+                .unwrap();
+                write_deprecation(&mut body, component.deprecation());
+                write_description(&mut body, component.description());
+                self.write_children(&mut body, component);
+            }
+            for event in component.events() {
+                self.write_event(&mut body, component, event);
+            }
+        }
+        self.page(&format!("Module {}", module_title(module)), &body)
+    }
+
+    fn write_children(&self, out: &mut String, component: &Component) {
+        let children: Vec<&Component> = component
+            .children()
+            .iter()
+            .filter_map(|child| self.env.component(child))
+            .collect();
+        if children.is_empty() {
+            return;
+        }
+        writeln!(out, "<p>Children:</p>\n<ul>").unwrap();
+        for child in children {
+            writeln!(
+                out,
+                "<li><a href=\"#{}\">{}</a> <span class=\"kind\">{}</span></li>",
+                component_anchor(child),
+                escape(component_name(child)),
+                child.component_type().name()
+            )
+            .unwrap();
+        }
+        writeln!(out, "</ul>").unwrap();
+    }
+
+    fn write_event(&self, out: &mut String, component: &Component, event: &Event) {
+        writeln!(
+            out,
+            "<h3 id=\"{}\">{}</h3>",
+            event_anchor(component, event),
+            escape(event.name())
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "<p>Emitted by <code>{}</code>.</p>",
+            escape(&component.epath().to_string())
+        )
+        .unwrap();
+        write_deprecation(out, event.deprecation());
+        write_description(out, event.description());
+
+        if event.arguments().is_empty() {
+            writeln!(out, "<p><em>No parameters.</em></p>").unwrap();
+            return;
+        }
+
+        let described = event
+            .arguments()
+            .iter()
+            .any(|argument| !argument.description().is_empty() || argument.deprecation().is_some());
+        write!(
+            out,
+            "<table>\n<tr><th>Parameter</th><th>Type</th><th>Pattern</th><th>Example</th>"
+        )
+        .unwrap();
+        if described {
+            write!(out, "<th>Description</th>").unwrap();
+        }
+        writeln!(out, "</tr>").unwrap();
+        for argument in event.arguments() {
+            let type_ref = argument.type_ref();
+            let patterns: Vec<String> = self
+                .env
+                .patterns(type_ref)
+                .into_iter()
+                .map(|pattern| format!("<code>{}</code>", escape(pattern)))
+                .collect();
+            write!(
+                out,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td>",
+                parameter_name(argument),
+                type_link(type_ref),
+                patterns.join("<br>"),
+                self.env
+                    .example(type_ref)
+                    .map(|example| format!("<code>{}</code>", escape(example)))
+                    .unwrap_or_default()
+            )
+            .unwrap();
+            if described {
+                write!(out, "<td>{}</td>", parameter_description(argument)).unwrap();
+            }
+            writeln!(out, "</tr>").unwrap();
+        }
+        writeln!(out, "</table>").unwrap();
+    }
+
+    fn type_page(&self, definition: &TypeDefinition) -> String {
+        let identifier = definition.identifier();
+        let mut body = String::new();
+        writeln!(
+            body,
+            "<h1>Type <code>{}</code></h1>",
+            escape(identifier.name())
+        )
+        .unwrap();
+        writeln!(
+            body,
+            "<p><code>{}</code></p>",
+            escape(&identifier.to_string())
+        )
+        .unwrap();
+        write_deprecation(&mut body, definition.deprecation());
+        write_description(&mut body, definition.description().unwrap_or_default());
+
+        let chain: Vec<String> = self
+            .env
+            .alias_chain(identifier)
+            .iter()
+            .map(|definition| type_link(definition.identifier()))
+            .collect();
+        writeln!(
+            body,
+            "<h2>Alias chain</h2>\n<p>{}</p>",
+            chain.join(" &rarr; ")
+        )
+        .unwrap();
+
+        let patterns = self.env.patterns(identifier);
+        if !patterns.is_empty() {
+            writeln!(body, "<h2>Patterns</h2>\n<ul>").unwrap();
+            for pattern in patterns {
+                writeln!(body, "<li><code>{}</code></li>", escape(pattern)).unwrap();
+            }
+            writeln!(body, "</ul>").unwrap();
+        }
+        if let Some(example) = self.env.example(identifier) {
+            writeln!(
+                body,
+                "<h2>Example</h2>\n<p><code>{}</code></p>",
+                escape(example)
+            )
+            .unwrap();
+        }
+
+        writeln!(
+            body,
+            "<h2>Native bindings</h2>\n<table>\n<tr><th>Language</th><th>Type</th></tr>"
+        )
+        .unwrap();
+        let bindings = self.env.native_bindings(identifier);
+        for language in TargetLanguage::ALL {
+            let binding = bindings
+                .and_then(|bindings| bindings.get(&language))
+                .map(|binding| format!("<code>{}</code>", escape(binding)))
+                .unwrap_or_else(|| "<em>unbound</em>".to_string());
+            writeln!(
+                body,
+                "<tr><td>{}</td><td>{}</td></tr>",
+                language.name(),
+                binding
+            )
+            .unwrap();
+        }
+        writeln!(body, "</table>").unwrap();
+
+        let usages = self.usages(identifier);
+        if !usages.is_empty() {
+            writeln!(body, "<h2>Used by</h2>\n<ul>").unwrap();
+            for (component, event) in usages {
+                writeln!(
+                    body,
+                    "<li><a href=\"{}\">{}/{}</a></li>",
+                    self.event_url(component, event),
+                    escape(&component.epath().to_string()),
+                    escape(event.name())
+                )
+                .unwrap();
+            }
+            writeln!(body, "</ul>").unwrap();
+        }
+
+        self.page(&format!("Type {}", identifier.name()), &body)
+    }
+
+    /// Events with a parameter of the given type, directly or through an alias.
+    fn usages(&self, identifier: &TypeDefinitionIdentifier) -> Vec<(&'env Component, &'env Event)> {
+        self.env
+            .events()
+            .filter(|(_, event)| {
+                event.arguments().iter().any(|argument| {
+                    self.env
+                        .alias_chain(argument.type_ref())
+                        .iter()
+                        .any(|definition| definition.identifier() == identifier)
+                })
+            })
+            .collect()
+    }
+
+    fn search_index(&self) -> Vec<SearchEntry> {
+        let mut buf = Vec::new();
+        for module in self.env.modules() {
+            buf.push(SearchEntry {
+                title: module_title(module),
+                kind: "module",
+                url: module_url(module),
+                text: module.description().to_string(),
+            });
+            for component in self.env.component_tree(module) {
+                if component.epath() != module.epath() {
+                    buf.push(SearchEntry {
+                        title: component.epath().to_string(),
+                        kind: component.component_type().name(),
+                        url: format!("{}#{}", module_url(module), component_anchor(component)),
+                        text: component.description().to_string(),
+                    });
+                }
+                for event in component.events() {
+                    let parameters: Vec<&str> = event
+                        .arguments()
+                        .iter()
+                        .map(|argument| argument.name())
+                        .collect();
+                    buf.push(SearchEntry {
+                        title: event.name().to_string(),
+                        kind: "event",
+                        url: format!("{}#{}", module_url(module), event_anchor(component, event)),
+                        text: format!(
+                            "{} {} {}",
+                            component.epath().to_string(),
+                            parameters.join(" "),
+                            event.description()
+                        )
+                        .trim_end()
+                        .to_string(),
+                    });
+                }
+            }
+        }
+        for definition in self.env.type_definitions() {
+            buf.push(SearchEntry {
+                title: definition.identifier().name().to_string(),
+                kind: "type",
+                url: type_url(definition.identifier()),
+                text: format!(
+                    "{} {}",
+                    definition.identifier(),
+                    definition.description().unwrap_or_default()
+                )
+                .trim_end()
+                .to_string(),
+            });
+        }
+        buf
+    }
+
+    fn event_url(&self, component: &Component, event: &Event) -> String {
+        let module = self
+            .env
+            .modules()
+            .find(|module| {
+                self.env
+                    .component_tree(module)
+                    .iter()
+                    .any(|candidate| candidate.epath() == component.epath())
+            })
+            .unwrap_or(component);
+        format!("{}#{}", module_url(module), event_anchor(component, event))
+    }
+
+    fn page(&self, title: &str, body: &str) -> String {
+        let mut nav = String::new();
+        writeln!(
+            nav,
+            "<p><a href=\"index.html\">{}</a></p>",
+            escape(&self.env.identifier().to_string())
+        )
+        .unwrap();
+        writeln!(
+            nav,
+            "<input id=\"search\" type=\"search\" placeholder=\"Search\">"
+        )
+        .unwrap();
+        writeln!(nav, "<ul id=\"search-results\"></ul>").unwrap();
+        writeln!(nav, "<ul>").unwrap();
+        for module in self.env.modules() {
+            writeln!(
+                nav,
+                "<li><a href=\"{}\">{}</a>",
+                module_url(module),
+                escape(&module_title(module))
+            )
+            .unwrap();
+            self.write_nav_tree(&mut nav, module, module);
+            writeln!(nav, "</li>").unwrap();
+        }
+        writeln!(nav, "</ul>").unwrap();
+
+        format!(
+            "<!DOCTYPE html>
+<html lang=\"en\">
+<head>
+<meta charset=\"utf-8\">
+<title>{}</title>
+<link rel=\"stylesheet\" href=\"style.css\">
+</head>
+<body>
+<nav>
+{}</nav>
+<main>
+{}</main>
+<script src=\"search-index.js\"></script>
+<script src=\"search.js\"></script>
+</body>
+</html>
+",
+            escape(title),
+            nav,
+            body
+        )
+    }
+
+    fn write_nav_tree(&self, out: &mut String, module: &Component, component: &Component) {
+        if component.children().is_empty() {
+            return;
+        }
+        writeln!(out, "<ul>").unwrap();
+        for child in component.children() {
+            if let Some(child) = self.env.component(child) {
+                writeln!(
+                    out,
+                    "<li><a href=\"{}#{}\">{}</a>",
+                    module_url(module),
+                    component_anchor(child),
+                    escape(component_name(child))
+                )
+                .unwrap();
+                self.write_nav_tree(out, module, child);
+                writeln!(out, "</li>").unwrap();
+            }
+        }
+        writeln!(out, "</ul>").unwrap();
+    }
+}
+
+fn asset(path: &str) -> String {
+    Assets::get(path)
+        .map(|file| String::from_utf8_lossy(file.data.as_ref()).into_owned())
+        .unwrap_or_default()
+}
+
+/// Deprecation notice of a declaration, nothing for current ones.
+fn write_deprecation(out: &mut String, deprecation: Option<&Deprecation>) {
+    if let Some(deprecation) = deprecation {
+        writeln!(
+            out,
+            "<p class=\"deprecated\"><strong>{}</strong></p>",
+            escape(&deprecation.message())
+        )
+        .unwrap();
+    }
+}
+
+/// Name of a parameter, struck through when it is deprecated.
+fn parameter_name(argument: &EventArgument) -> String {
+    let name = format!("<code>{}</code>", escape(argument.name()));
+    match argument.deprecation() {
+        Some(_) => format!("<del>{}</del>", name),
+        None => name,
+    }
+}
+
+/// Description of a parameter, followed by the deprecation notice of deprecated ones.
+fn parameter_description(argument: &EventArgument) -> String {
+    let description = escape(argument.description().trim()).replace('\n', "<br>");
+    let Some(deprecation) = argument.deprecation() else {
+        return description;
+    };
+    let notice = format!(
+        "<strong class=\"deprecated\">{}</strong>",
+        escape(&deprecation.message())
+    );
+    match description.is_empty() {
+        true => notice,
+        false => format!("{}<br>{}", description, notice),
+    }
+}
+
+/// Markdown description as escaped paragraphs, blank lines separating them.
+fn write_description(out: &mut String, description: &str) {
+    for paragraph in description.split("\n\n") {
+        let paragraph = paragraph.trim();
+        if !paragraph.is_empty() {
+            writeln!(
+                out,
+                "<p class=\"description\">{}</p>",
+                escape(paragraph).replace('\n', "<br>")
+            )
+            .unwrap();
+        }
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// `parts` escaped with [`escape_identifier`] and joined with `-`, e.g. `tuna_2dbank` for
+/// `tuna-bank`. As escaped parts never contain `-`, different parts always yield different slugs.
+fn slug<'a, I: IntoIterator<Item = &'a str>>(parts: I) -> String {
+    parts
+        .into_iter()
+        .map(escape_identifier)
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
+fn module_title(module: &Component) -> String {
+    if module.epath().segments.is_empty() {
+        "/".to_string()
+    } else {
+        module.epath().to_string()
+    }
+}
+
+fn module_url(module: &Component) -> String {
+    format!(
+        "module-{}.html",
+        slug(
+            ["root"]
+                .into_iter()
+                .chain(module.epath().segments.iter().map(String::as_str))
+        )
+    )
+}
+
+fn component_name(component: &Component) -> &str {
+    component
+        .epath()
+        .segments
+        .last()
+        .map(String::as_str)
+        .unwrap_or("")
+}
+
+fn component_anchor(component: &Component) -> String {
+    slug(
+        ["component"]
+            .into_iter()
+            .chain(component.epath().segments.iter().map(String::as_str)),
+    )
+}
+
+fn event_anchor(component: &Component, event: &Event) -> String {
+    slug(
+        ["event"]
+            .into_iter()
+            .chain(component.epath().segments.iter().map(String::as_str))
+            .chain([event.name()]),
+    )
+}
+
+fn type_url(identifier: &TypeDefinitionIdentifier) -> String {
+    format!(
+        "type-{}.html",
+        slug(
+            [identifier.project().group(), identifier.project().name()]
+                .into_iter()
+                .chain(identifier.path().segments.iter().map(String::as_str))
+        )
+    )
+}
+
+fn type_link(identifier: &TypeDefinitionIdentifier) -> String {
+    format!(
+        "<a href=\"{}\"><code>{}</code></a>",
+        type_url(identifier),
+        escape(identifier.name())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::TypeConstraints;
+    use crate::util::{example_environment, type_identifier};
+
+    fn file<'a>(files: &'a [ExportedFile], path: &str) -> &'a str {
+        files
+            .iter()
+            .find(|file| file.path().to_string_lossy() == path)
+            .unwrap()
+            .content()
+    }
+
+    #[test]
+    fn test_site_files() {
+        let env = example_environment();
+        let files = HtmlSiteExporter::of(&env).export().unwrap();
+
+        assert!(file(&files, "style.css").contains("body"));
+        assert!(file(&files, "search.js").contains("AMS_SEARCH_INDEX"));
+        assert!(
+            file(&files, "index.html").contains("<a href=\"module-root-common.html\">common</a>")
+        );
+    }
+
+    #[test]
+    fn test_event_links_to_types() {
+        let env = example_environment();
+        let files = HtmlSiteExporter::of(&env).export().unwrap();
+        let module = file(&files, "module-root-common.html");
+
+        assert!(module.contains("<h3 id=\"event-common-PhoneLoginPage-OnLogin\">OnLogin</h3>"));
+        assert!(module.contains(
+            "<a href=\"type-tuna_2dbank-client_2dapp-common-PhoneNumber.html\"><code>PhoneNumber</code></a>"
+        ));
+    }
+
+    #[test]
+    fn test_type_page() {
+        let env = example_environment();
+        let files = HtmlSiteExporter::of(&env).export().unwrap();
+        let page = file(
+            &files,
+            "type-tuna_2dbank-client_2dapp-common-PhoneNumber.html",
+        );
+
+        assert!(page.contains(
+            "<a href=\"type-tuna_2dbank-client_2dapp-common-PhoneNumber.html\"><code>PhoneNumber</code></a> &rarr; <a href=\"type-ams-std-String.html\"><code>String</code></a>"
+        ));
+        assert!(page.contains("<tr><td>Kotlin</td><td><code>kotlin.String</code></td></tr>"));
+        assert!(page.contains(
+            "<a href=\"module-root-common.html#event-common-PhoneLoginPage-OnLogin\">common/PhoneLoginPage/OnLogin</a>"
+        ));
+    }
+
+    #[test]
+    fn test_search_index() {
+        let env = example_environment();
+        let files = HtmlSiteExporter::of(&env).export().unwrap();
+        let index = file(&files, "search-index.js");
+        let entries: serde_json::Value = serde_json::from_str(
+            index
+                .trim_start_matches("window.AMS_SEARCH_INDEX = ")
+                .trim_end_matches(";\n"),
+        )
+        .unwrap();
+
+        assert!(entries
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|entry| entry["title"] == "OnLogin" && entry["kind"] == "event"));
+    }
+
+    #[test]
+    fn test_slug_is_unique() {
+        assert_eq!(slug(["event", "cart", "OnAdd"]), "event-cart-OnAdd");
+        assert_ne!(slug(["a-b", "c"]), slug(["a", "b-c"]));
+        assert_ne!(slug(["a-b"]), slug(["a", "b"]));
+        assert_ne!(slug(["a_b"]), slug(["a-b"]));
+        assert_eq!(slug(["tuna-bank", "é"]), "tuna_2dbank-_c3_a9");
+    }
+
+    #[test]
+    fn test_type_description() {
+        let mut env = example_environment();
+        env.push_type_definition(
+            TypeDefinition::new_object(
+                type_identifier("tuna-bank:client-app", "cart/Item"),
+                vec![],
+                None,
+                None,
+                TypeConstraints::default(),
+            )
+            .with_description(Some("Item of a <cart>.\n\nPriced per unit.")),
+        );
+        let files = HtmlSiteExporter::of(&env).export().unwrap();
+        let page = file(&files, "type-tuna_2dbank-client_2dapp-cart-Item.html");
+
+        assert!(page.contains(
+            "<p class=\"description\">Item of a &lt;cart&gt;.</p>\n<p class=\"description\">Priced per unit.</p>"
+        ));
+        assert!(file(&files, "search-index.js").contains("Priced per unit."));
+    }
+
+    #[test]
+    fn test_type_deprecation() {
+        let mut env = example_environment();
+        env.push_type_definition(
+            TypeDefinition::new_object(
+                type_identifier("tuna-bank:client-app", "cart/Item"),
+                vec![],
+                None,
+                None,
+                TypeConstraints::default(),
+            )
+            .with_deprecation(Some(Deprecation::new(
+                None,
+                Some("Carts are gone"),
+                None,
+            ))),
+        );
+        let files = HtmlSiteExporter::of(&env).export().unwrap();
+        let page = file(&files, "type-tuna_2dbank-client_2dapp-cart-Item.html");
+
+        assert!(page
+            .contains("<p class=\"deprecated\"><strong>Deprecated: Carts are gone.</strong></p>"));
+    }
+}
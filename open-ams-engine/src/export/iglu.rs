@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+
+use crate::entity::{Component, Environment, Event};
+use crate::export::naming::event_table_name;
+use crate::export::{ExportError, ExportedFile, JsonSchemaExporter};
+use crate::source::entity::Version;
+
+pub const IGLU_SELF_DESCRIBING_SCHEMA: &str =
+    "http://iglucentral.com/schemas/com.snowplowanalytics.self-desc/schema/jsonschema/1-0-0#";
+const IGLU_FORMAT: &str = "jsonschema";
+
+/// Exports every resolved event as a Snowplow self-describing JSON schema, laid out as a static
+/// Iglu repository: `schemas/<vendor>/<name>/jsonschema/<version>`.
+#[derive(Debug)]
+pub struct IgluExporter<'env> {
+    env: &'env Environment,
+}
+
+impl<'env> IgluExporter<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        IgluExporter { env }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let mut buf = Vec::new();
+        for (component, event) in self.env.events() {
+            let path: PathBuf = [
+                "schemas",
+                &self.vendor(),
+                &event_table_name(component, event),
+                IGLU_FORMAT,
+                &self.version(),
+            ]
+            .iter()
+            .collect();
+
+            buf.push(ExportedFile::new(
+                path,
+                serde_json::to_string_pretty(&self.event_schema(component, event))?,
+            ));
+        }
+        Ok(buf)
+    }
+
+    /// Vendor of the schemas, the project group limited to the characters Iglu accepts.
+    pub fn vendor(&self) -> String {
+        self.env
+            .identifier()
+            .group()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '.' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    /// SchemaVer `MODEL-REVISION-ADDITION` of the project version. Models start at 1, so `0.x.y`
+    /// versions map to `1-x-y`.
+    pub fn version(&self) -> String {
+        match self.env.version() {
+            Version::Semver {
+                major,
+                minor,
+                patch,
+            } => format!("{}-{}-{}", (*major).max(1), minor, patch),
+            Version::Latest => "1-0-0".to_string(),
+        }
+    }
+
+    pub fn event_schema(&self, component: &Component, event: &Event) -> Value {
+        let mut schema = json!({
+            "$schema": IGLU_SELF_DESCRIBING_SCHEMA,
+            "description": format!("{} emitted by {}", event.name(), component.epath().to_string()),
+            "self": {
+                "vendor": self.vendor(),
+                "name": event_table_name(component, event),
+                "format": IGLU_FORMAT,
+                "version": self.version(),
+            },
+        });
+        if let (Value::Object(schema), Value::Object(payload)) = (
+            &mut schema,
+            JsonSchemaExporter::of(self.env).event_schema(event),
+        ) {
+            schema.extend(payload);
+        }
+        schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::example_environment;
+    use std::path::Path;
+
+    #[test]
+    fn test_files() {
+        let env = example_environment();
+        let files = IgluExporter::of(&env).export().unwrap();
+
+        assert_eq!(files.len(), 3);
+        assert_eq!(
+            files[1].path(),
+            Path::new("schemas/tuna-bank/common_phone_login_page_on_login/jsonschema/1-1-0")
+        );
+    }
+
+    #[test]
+    fn test_event_schema() {
+        let env = example_environment();
+        let (component, event) = env.events().find(|(_, e)| e.name() == "OnLogin").unwrap();
+        let schema = IgluExporter::of(&env).event_schema(component, event);
+
+        assert_eq!(schema["$schema"], IGLU_SELF_DESCRIBING_SCHEMA);
+        assert_eq!(
+            schema["self"],
+            json!({
+                "vendor": "tuna-bank",
+                "name": "common_phone_login_page_on_login",
+                "format": "jsonschema",
+                "version": "1-1-0",
+            })
+        );
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["required"], json!(["passcode", "phone"]));
+        assert_eq!(schema["additionalProperties"], false);
+    }
+}
@@ -0,0 +1,587 @@
+use serde_json::{json, Map, Value};
+
+use crate::entity::{
+    CollectionKind, Deprecation, Environment, Event, ObjectTypeDefinition, PrimitiveType,
+    TypeDefinitionIdentifier,
+};
+use crate::export::{ExportError, ExportedFile};
+
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Exports every resolved event as a standalone JSON Schema (draft 2020-12) document.
+#[derive(Debug)]
+pub struct JsonSchemaExporter<'env> {
+    env: &'env Environment,
+}
+
+impl<'env> JsonSchemaExporter<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        JsonSchemaExporter { env }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let mut buf = Vec::new();
+        for (component, event) in self.env.events() {
+            let path = ExportedFile::event_path(component, event, "schema.json");
+            let mut schema = self.event_schema(event);
+            schema["$schema"] = json!(JSON_SCHEMA_DIALECT);
+            schema["$id"] = json!(path.to_string_lossy().replace('\\', "/"));
+            schema["title"] = json!(format!(
+                "{}/{}",
+                component.epath().to_string(),
+                event.name()
+            ));
+
+            buf.push(ExportedFile::new(
+                path,
+                serde_json::to_string_pretty(&schema)?,
+            ));
+        }
+        Ok(buf)
+    }
+
+    /// Object schema describing the payload of `event`, without document level keywords. Only
+    /// required parameters are listed as `required`.
+    pub fn event_schema(&self, event: &Event) -> Value {
+        self.event_schema_at(event, "#")
+    }
+
+    /// Like [`Self::event_schema`] for a payload embedded at the JSON pointer `pointer` of a
+    /// larger document, e.g. `#/components/messages/OnLogin/payload`, so that references to
+    /// object definitions resolve against the document root.
+    pub fn event_schema_at(&self, event: &Event, pointer: &str) -> Value {
+        let mut definitions = Definitions::at(pointer);
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for argument in event.arguments() {
+            properties.insert(
+                argument.name().to_string(),
+                annotated(
+                    self.property_schema(
+                        argument.type_ref(),
+                        argument.required(),
+                        argument.default(),
+                        &mut definitions,
+                    ),
+                    argument.description(),
+                    argument.deprecation(),
+                ),
+            );
+            if argument.required() {
+                required.push(json!(argument.name()));
+            }
+        }
+
+        let mut schema = json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        });
+        definitions.attach(&mut schema);
+        annotated(schema, event.description(), event.deprecation())
+    }
+
+    /// Schema of a type with its alias chain flattened: the JSON type comes from the terminal
+    /// definition, every pattern met along the chain has to match and constraints are narrowed
+    /// as described by [`Environment::constraints`]. Objects are referenced from `$defs`.
+    pub fn type_schema(&self, identifier: &TypeDefinitionIdentifier) -> Value {
+        let mut definitions = Definitions::at("#");
+        let mut schema = self.schema(identifier, &mut definitions);
+        definitions.attach(&mut schema);
+        schema
+    }
+
+    /// Schema of a parameter or field, optional ones also accept `null` and carry their default.
+    fn property_schema(
+        &self,
+        type_ref: &TypeDefinitionIdentifier,
+        required: bool,
+        default: Option<&Value>,
+        definitions: &mut Definitions,
+    ) -> Value {
+        let schema = self.schema(type_ref, definitions);
+        if required {
+            return schema;
+        }
+        let mut optional = Map::new();
+        optional.insert("anyOf".to_string(), json!([schema, { "type": "null" }]));
+        if let Some(default) = default {
+            optional.insert("default".to_string(), default.clone());
+        }
+        Value::Object(optional)
+    }
+
+    /// Closed object schema requiring every required field of the object.
+    fn object_schema(&self, object: &ObjectTypeDefinition, definitions: &mut Definitions) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in object.fields() {
+            properties.insert(
+                field.name().to_string(),
+                annotated(
+                    self.property_schema(
+                        field.type_ref(),
+                        field.required(),
+                        field.default(),
+                        definitions,
+                    ),
+                    field.description().unwrap_or_default(),
+                    field.deprecation(),
+                ),
+            );
+            if field.required() {
+                required.push(json!(field.name()));
+            }
+        }
+
+        json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+            "additionalProperties": false,
+        })
+    }
+
+    /// Objects are defined once in `definitions` and referenced, which keeps recursive objects
+    /// finite.
+    fn schema(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+        definitions: &mut Definitions,
+    ) -> Value {
+        if let Some(object_definition) = self.env.object_definition(identifier) {
+            let name = definition_name(identifier);
+            if !definitions.contains(&name) {
+                definitions.reserve(&name);
+                let schema = self.object_schema(object_definition, definitions);
+                definitions.define(&name, schema);
+            }
+            return definitions.reference(&name);
+        }
+        if let Some(collection) = self.env.collection_definition(identifier) {
+            let element = self.schema(collection.element(), definitions);
+            return match collection.kind() {
+                CollectionKind::List => json!({ "type": "array", "items": element }),
+                CollectionKind::Map => json!({ "type": "object", "additionalProperties": element }),
+            };
+        }
+
+        let mut schema = Map::new();
+        if let Some(json_type) = self.env.primitive_type(identifier).map(json_type) {
+            schema.insert("type".to_string(), json!(json_type));
+        }
+
+        if let Some(enum_definition) = self.env.enum_definition(identifier) {
+            let primitive_type = self.env.primitive_type(identifier);
+            let values: Vec<Value> = enum_definition
+                .values()
+                .iter()
+                .map(|value| typed_value(primitive_type, value.value()))
+                .collect();
+            schema.insert("enum".to_string(), Value::Array(values));
+        }
+
+        let patterns = self.env.patterns(identifier);
+        match patterns.as_slice() {
+            [] => {}
+            [pattern] => {
+                schema.insert("pattern".to_string(), json!(pattern));
+            }
+            _ => {
+                let all_of: Vec<Value> = patterns.iter().map(|p| json!({ "pattern": p })).collect();
+                schema.insert("allOf".to_string(), json!(all_of));
+            }
+        }
+
+        let constraints = self.env.constraints(identifier);
+        if let Some(min_length) = constraints.min_length {
+            schema.insert("minLength".to_string(), json!(min_length));
+        }
+        if let Some(max_length) = constraints.max_length {
+            schema.insert("maxLength".to_string(), json!(max_length));
+        }
+        if let Some(minimum) = constraints.minimum {
+            schema.insert("minimum".to_string(), json_number(minimum));
+        }
+        if let Some(maximum) = constraints.maximum {
+            schema.insert("maximum".to_string(), json_number(maximum));
+        }
+        if let Some(multiple_of) = constraints.multiple_of {
+            schema.insert("multipleOf".to_string(), json_number(multiple_of));
+        }
+        if let Some(format) = constraints.format {
+            schema.insert("format".to_string(), json!(format.name()));
+        }
+
+        Value::Object(schema)
+    }
+}
+
+/// Object definitions collected while building a schema, emitted as its `$defs`.
+struct Definitions {
+    pointer: String,
+    definitions: Map<String, Value>,
+}
+
+impl Definitions {
+    fn at(pointer: &str) -> Self {
+        Definitions {
+            pointer: pointer.to_string(),
+            definitions: Map::new(),
+        }
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.definitions.contains_key(name)
+    }
+
+    /// Marks `name` as being defined, so that recursive references don't define it again.
+    fn reserve(&mut self, name: &str) {
+        self.definitions.insert(name.to_string(), Value::Null);
+    }
+
+    fn define(&mut self, name: &str, schema: Value) {
+        self.definitions.insert(name.to_string(), schema);
+    }
+
+    fn reference(&self, name: &str) -> Value {
+        json!({ "$ref": format!("{}/$defs/{}", self.pointer, name) })
+    }
+
+    fn attach(self, schema: &mut Value) {
+        if let (Value::Object(schema), false) = (schema, self.definitions.is_empty()) {
+            schema.insert("$defs".to_string(), Value::Object(self.definitions));
+        }
+    }
+}
+
+/// Name of an object in `$defs`, e.g. `tuna-bank.client-app.common.CartItem`.
+fn definition_name(identifier: &TypeDefinitionIdentifier) -> String {
+    let project = identifier.project();
+    [project.group(), project.name()]
+        .into_iter()
+        .chain(identifier.path().segments.iter().map(String::as_str))
+        .collect::<Vec<&str>>()
+        .join(".")
+}
+
+/// `schema` annotated with a `description` and flagged as `deprecated`, when there is reason to.
+fn annotated(mut schema: Value, description: &str, deprecation: Option<&Deprecation>) -> Value {
+    if let Value::Object(map) = &mut schema {
+        if !description.is_empty() {
+            map.insert("description".to_string(), json!(description));
+        }
+        if deprecation.is_some() {
+            map.insert("deprecated".to_string(), json!(true));
+        }
+    }
+    schema
+}
+
+/// Enum values are declared as strings, numeric enums get them as JSON numbers.
+fn typed_value(primitive_type: Option<PrimitiveType>, value: &str) -> Value {
+    match primitive_type {
+        Some(PrimitiveType::Integer | PrimitiveType::Long) => {
+            value.parse::<i64>().map(Value::from).ok()
+        }
+        Some(PrimitiveType::Float) => value.parse::<f64>().map(json_number).ok(),
+        _ => None,
+    }
+    .unwrap_or_else(|| json!(value))
+}
+
+/// Whole numbers are written without a fraction, `150` rather than `150.0`.
+fn json_number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        json!(value as i64)
+    } else {
+        json!(value)
+    }
+}
+
+fn json_type(primitive_type: PrimitiveType) -> &'static str {
+    match primitive_type {
+        PrimitiveType::String => "string",
+        PrimitiveType::Integer | PrimitiveType::Long => "integer",
+        PrimitiveType::Float => "number",
+        PrimitiveType::Boolean => "boolean",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EnumValue, EventArgument, ObjectField, TypeConstraints, TypeDefinition};
+    use crate::util::{example_environment, std_type, type_identifier};
+    use std::path::Path;
+
+    #[test]
+    fn test_exports_component_and_module_events() {
+        let env = example_environment();
+        let files = JsonSchemaExporter::of(&env).export().unwrap();
+        let paths: Vec<&Path> = files.iter().map(|file| file.path()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                Path::new("common/UserLoggedIn.schema.json"),
+                Path::new("common/PhoneLoginPage/OnLogin.schema.json"),
+                Path::new("common/PhoneLoginPage/LogingButton/OnClick.schema.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_event_schema_follows_alias_chain() {
+        let env = example_environment();
+        let (_, event) = env.events().find(|(_, e)| e.name() == "OnLogin").unwrap();
+        let schema = JsonSchemaExporter::of(&env).event_schema(event);
+
+        assert_eq!(
+            schema,
+            json!({
+                "type": "object",
+                "properties": {
+                    "passcode": { "type": "string" },
+                    "phone": { "type": "string", "pattern": "^\\+?[0-9]{1,3}-?[0-9]{1,14}$" },
+                },
+                "required": ["passcode", "phone"],
+                "additionalProperties": false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_type_schema_constraints() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_alias(
+            std_type("Age"),
+            std_type("Integer"),
+            None,
+            None,
+            TypeConstraints {
+                minimum: Some(0.0),
+                maximum: Some(150.0),
+                ..Default::default()
+            },
+        ));
+        env.push_type_definition(TypeDefinition::new_alias(
+            std_type("Ratio"),
+            std_type("Float"),
+            None,
+            None,
+            TypeConstraints {
+                multiple_of: Some(0.5),
+                ..Default::default()
+            },
+        ));
+
+        let exporter = JsonSchemaExporter::of(&env);
+        assert_eq!(
+            exporter.type_schema(&std_type("Age")),
+            json!({ "type": "integer", "minimum": 0, "maximum": 150 })
+        );
+        assert_eq!(
+            exporter.type_schema(&std_type("Ratio")),
+            json!({ "type": "number", "multipleOf": 0.5 })
+        );
+    }
+
+    #[test]
+    fn test_type_schema_enum() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_enum(
+            type_identifier("tuna-bank:client-app", "Priority"),
+            Some(std_type("Integer")),
+            vec![EnumValue::new("1", Some("Low")), EnumValue::new("2", None)],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+
+        assert_eq!(
+            JsonSchemaExporter::of(&env)
+                .type_schema(&type_identifier("tuna-bank:client-app", "Priority")),
+            json!({ "type": "integer", "enum": [1, 2] })
+        );
+    }
+
+    #[test]
+    fn test_type_schema_object() {
+        let mut env = example_environment();
+        let cart_item = type_identifier("tuna-bank:client-app", "CartItem");
+        env.push_type_definition(TypeDefinition::new_object(
+            cart_item.clone(),
+            vec![
+                ObjectField::new("quantity", std_type("Integer")),
+                ObjectField::new(
+                    "phone",
+                    type_identifier("tuna-bank:client-app", "common/PhoneNumber"),
+                ),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+
+        assert_eq!(
+            JsonSchemaExporter::of(&env).type_schema(&cart_item),
+            json!({
+                "$ref": "#/$defs/tuna-bank.client-app.CartItem",
+                "$defs": {
+                    "tuna-bank.client-app.CartItem": {
+                        "type": "object",
+                        "properties": {
+                            "quantity": { "type": "integer" },
+                            "phone": {
+                                "type": "string",
+                                "pattern": "^\\+?[0-9]{1,3}-?[0-9]{1,14}$",
+                            },
+                        },
+                        "required": ["quantity", "phone"],
+                        "additionalProperties": false,
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_recursive_object() {
+        let mut env = example_environment();
+        let node = type_identifier("tuna-bank:client-app", "tree/Node");
+        let children = TypeDefinitionIdentifier::collection(CollectionKind::List, &node);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            node.clone(),
+        ));
+        env.push_type_definition(TypeDefinition::new_object(
+            node.clone(),
+            vec![
+                ObjectField::new("name", std_type("String")),
+                ObjectField::new_optional("parent", node.clone(), None),
+                ObjectField::new("children", children),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let event = Event::new("OnView", vec![EventArgument::new("root", node)]);
+        let reference = json!({ "$ref": "#/$defs/tuna-bank.client-app.tree.Node" });
+
+        assert_eq!(
+            JsonSchemaExporter::of(&env).event_schema(&event),
+            json!({
+                "type": "object",
+                "properties": { "root": reference },
+                "required": ["root"],
+                "additionalProperties": false,
+                "$defs": {
+                    "tuna-bank.client-app.tree.Node": {
+                        "type": "object",
+                        "properties": {
+                            "name": { "type": "string" },
+                            "parent": { "anyOf": [reference, { "type": "null" }] },
+                            "children": { "type": "array", "items": reference },
+                        },
+                        "required": ["name", "children"],
+                        "additionalProperties": false,
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_type_schema_collections() {
+        let mut env = example_environment();
+        let phone_number = type_identifier("tuna-bank:client-app", "common/PhoneNumber");
+        let list = TypeDefinitionIdentifier::collection(CollectionKind::List, &phone_number);
+        let map = TypeDefinitionIdentifier::collection(CollectionKind::Map, &list);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            phone_number,
+        ));
+        env.push_type_definition(TypeDefinition::new_collection(CollectionKind::Map, list));
+
+        assert_eq!(
+            JsonSchemaExporter::of(&env).type_schema(&map),
+            json!({
+                "type": "object",
+                "additionalProperties": {
+                    "type": "array",
+                    "items": {
+                        "type": "string",
+                        "pattern": "^\\+?[0-9]{1,3}-?[0-9]{1,14}$",
+                    },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_optional_parameters() {
+        let env = example_environment();
+        let integer = std_type("Integer");
+        let event = Event::new(
+            "OnSearch",
+            vec![
+                EventArgument::new("page", integer.clone()),
+                EventArgument::new_optional("size", integer, Some(json!(20))),
+            ],
+        );
+
+        assert_eq!(
+            JsonSchemaExporter::of(&env).event_schema(&event),
+            json!({
+                "type": "object",
+                "properties": {
+                    "page": { "type": "integer" },
+                    "size": {
+                        "anyOf": [{ "type": "integer" }, { "type": "null" }],
+                        "default": 20,
+                    },
+                },
+                "required": ["page"],
+                "additionalProperties": false,
+            })
+        );
+    }
+
+    #[test]
+    fn test_document_keywords() {
+        let env = example_environment();
+        let files = JsonSchemaExporter::of(&env).export().unwrap();
+        let document: Value = serde_json::from_str(files[0].content()).unwrap();
+
+        assert_eq!(document["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(document["$id"], "common/UserLoggedIn.schema.json");
+        assert_eq!(document["title"], "common/UserLoggedIn");
+    }
+
+    #[test]
+    fn test_annotations() {
+        let env = example_environment();
+        let string = std_type("String");
+        let event = Event::new(
+            "OnSearch",
+            vec![EventArgument::new("query", string)
+                .with_description("Text typed by the user")
+                .with_deprecation(Some(Deprecation::new(None, None, None)))],
+        )
+        .with_description("User searched the catalog");
+        let schema = JsonSchemaExporter::of(&env).event_schema(&event);
+
+        assert_eq!(schema["description"], json!("User searched the catalog"));
+        assert_eq!(schema.get("deprecated"), None);
+        assert_eq!(
+            schema["properties"]["query"],
+            json!({
+                "type": "string",
+                "description": "Text typed by the user",
+                "deprecated": true,
+            })
+        );
+    }
+}
@@ -0,0 +1,446 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+
+use crate::entity::{
+    Component, Deprecation, Environment, Event, EventArgument, TypeDefinitionIdentifier,
+};
+use crate::export::{ExportError, ExportedFile};
+
+/// Exports a Markdown catalog of the tracking plan: an index page and a page per module with a
+/// section per top level component, its component tree and a parameter table per event.
+#[derive(Debug)]
+pub struct MarkdownExporter<'env> {
+    env: &'env Environment,
+}
+
+impl<'env> MarkdownExporter<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        MarkdownExporter { env }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        let mut buf = vec![ExportedFile::new("index.md", self.index())];
+        for module in self.env.modules() {
+            buf.push(ExportedFile::new(
+                module_page_path(module),
+                self.module_page(module),
+            ));
+        }
+        Ok(buf)
+    }
+
+    fn index(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "# {}\n", self.env.identifier()).unwrap();
+        writeln!(
+            out,
+            "Version `{}`\n",
+            self.env.version().to_string()
+        )
+        .unwrap();
+        writeln!(out, "## Modules\n").unwrap();
+        for module in self.env.modules() {
+            writeln!(
+                out,
+                "- [{}]({})",
+                module_title(module),
+                module_page_path(module)
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    pub fn module_page(&self, module: &Component) -> String {
+        let mut out = String::new();
+        writeln!(out, "# Module `{}`", module_title(module)).unwrap();
+        write_description(&mut out, module.description());
+
+        if !module.events().is_empty() {
+            writeln!(out, "\n## Events").unwrap();
+            for event in module.events() {
+                self.write_event(&mut out, module, event);
+            }
+        }
+
+        for child in module.children() {
+            let Some(component) = self.env.component(child) else {
+                continue;
+            };
+            writeln!(
+                out,
+                "\n## {} `{}`\n",
+                capitalized(component.component_type().name()),
+                component_name(component)
+            )
+            .unwrap();
+            write_deprecation(&mut out, component.deprecation());
+            if !component.description().is_empty() {
+                writeln!(out, "{}\n", component.description().trim_end()).unwrap();
+            }
+            writeln!(out, "```text").unwrap();
+            self.write_tree(&mut out, component, "", "");
+            writeln!(out, "```").unwrap();
+
+            for descendant in self.env.component_tree(component) {
+                for event in descendant.events() {
+                    self.write_event(&mut out, descendant, event);
+                }
+            }
+        }
+        out
+    }
+
+    fn write_tree(
+        &self,
+        out: &mut String,
+        component: &Component,
+        prefix: &str,
+        child_prefix: &str,
+    ) {
+        writeln!(
+            out,
+            "{}{} ({})",
+            prefix,
+            component_name(component),
+            component.component_type().name()
+        )
+        .unwrap();
+
+        let children: Vec<&Component> = component
+            .children()
+            .iter()
+            .filter_map(|child| self.env.component(child))
+            .collect();
+        for (index, child) in children.iter().enumerate() {
+            let (branch, indent) = if index + 1 == children.len() {
+                ("└── ", "    ")
+            } else {
+                ("├── ", "│   ")
+            };
+            self.write_tree(
+                out,
+                child,
+                &format!("{}{}", child_prefix, branch),
+                &format!("{}{}", child_prefix, indent),
+            );
+        }
+    }
+
+    fn write_event(&self, out: &mut String, component: &Component, event: &Event) {
+        writeln!(out, "\n### {}\n", event.name()).unwrap();
+        writeln!(
+            out,
+            "Emitted by `{}`.\n",
+            component.epath().to_string()
+        )
+        .unwrap();
+        write_deprecation(out, event.deprecation());
+        if !event.description().is_empty() {
+            writeln!(out, "{}\n", event.description().trim_end()).unwrap();
+        }
+
+        if event.arguments().is_empty() {
+            writeln!(out, "_No parameters._").unwrap();
+            return;
+        }
+
+        let described = event
+            .arguments()
+            .iter()
+            .any(|argument| !argument.description().is_empty() || argument.deprecation().is_some());
+        match described {
+            true => {
+                writeln!(
+                    out,
+                    "| Parameter | Type | Pattern | Example | Description |"
+                )
+                .unwrap();
+                writeln!(out, "|---|---|---|---|---|").unwrap();
+            }
+            false => {
+                writeln!(out, "| Parameter | Type | Pattern | Example |").unwrap();
+                writeln!(out, "|---|---|---|---|").unwrap();
+            }
+        }
+        for argument in event.arguments() {
+            let type_ref = argument.type_ref();
+            let patterns: Vec<String> = self.env.patterns(type_ref).into_iter().map(code).collect();
+            write!(
+                out,
+                "| {} | {} | {} | {} |",
+                parameter_name(argument),
+                self.resolved_type(type_ref),
+                patterns.join("<br>"),
+                self.env.example(type_ref).map(code).unwrap_or_default(),
+            )
+            .unwrap();
+            if described {
+                write!(out, " {} |", parameter_description(argument)).unwrap();
+            }
+            writeln!(out).unwrap();
+        }
+    }
+
+    /// Alias chain of the type, e.g. `` `PhoneNumber` → `String` ``.
+    fn resolved_type(&self, identifier: &TypeDefinitionIdentifier) -> String {
+        let chain = self.env.alias_chain(identifier);
+        if chain.is_empty() {
+            return code(&identifier.to_string());
+        }
+        chain
+            .iter()
+            .map(|definition| code(definition.identifier().name()))
+            .collect::<Vec<String>>()
+            .join(" → ")
+    }
+}
+
+fn module_title(module: &Component) -> String {
+    if module.epath().segments.is_empty() {
+        "/".to_string()
+    } else {
+        module.epath().to_string()
+    }
+}
+
+fn module_page_path(module: &Component) -> PathBuf {
+    if module.epath().segments.is_empty() {
+        return PathBuf::from("root.md");
+    }
+    let mut path: PathBuf = module.epath().segments.iter().collect();
+    path.set_extension("md");
+    path
+}
+
+fn component_name(component: &Component) -> &str {
+    component
+        .epath()
+        .segments
+        .last()
+        .map(String::as_str)
+        .unwrap_or("")
+}
+
+fn capitalized(value: &str) -> String {
+    let mut chars = value.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Deprecation notice as its own paragraph, nothing for current declarations.
+fn write_deprecation(out: &mut String, deprecation: Option<&Deprecation>) {
+    if let Some(deprecation) = deprecation {
+        writeln!(out, "**{}**\n", deprecation.message()).unwrap();
+    }
+}
+
+/// Name of a parameter, struck through when it is deprecated.
+fn parameter_name(argument: &EventArgument) -> String {
+    match argument.deprecation() {
+        Some(_) => format!("~~`{}`~~", argument.name()),
+        None => format!("`{}`", argument.name()),
+    }
+}
+
+/// Markdown description as its own paragraph, nothing without one.
+fn write_description(out: &mut String, description: &str) {
+    if !description.trim().is_empty() {
+        writeln!(out, "\n{}", description.trim_end()).unwrap();
+    }
+}
+
+/// Description cell of a parameter, followed by the deprecation notice of deprecated ones.
+fn parameter_description(argument: &EventArgument) -> String {
+    let description = table_cell(argument.description());
+    let Some(deprecation) = argument.deprecation() else {
+        return description;
+    };
+    let notice = format!("**{}**", table_cell(&deprecation.message()));
+    match description.is_empty() {
+        true => notice,
+        false => format!("{}<br>{}", description, notice),
+    }
+}
+
+/// Multi-line Markdown squeezed into a single table cell.
+fn table_cell(value: &str) -> String {
+    value.trim().replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// Inline code span safe to use inside of a table cell.
+fn code(value: &str) -> String {
+    format!("`{}`", value.replace('|', "\\|"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{ComponentType, ProjectIdentifier};
+    use crate::source::entity::{EPath, Version};
+    use crate::util::{example_environment, std_type};
+    use std::path::Path;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_files() {
+        let env = example_environment();
+        let files = MarkdownExporter::of(&env).export().unwrap();
+        let paths: Vec<&Path> = files.iter().map(|file| file.path()).collect();
+
+        assert_eq!(paths, vec![Path::new("index.md"), Path::new("common.md")]);
+        assert!(files[0].content().contains("- [common](common.md)"));
+    }
+
+    #[test]
+    fn test_module_page() {
+        let env = example_environment();
+        let module = env.modules().next().unwrap();
+
+        assert_eq!(
+            MarkdownExporter::of(&env).module_page(module),
+            "# Module `common`
+
+## Events
+
+### UserLoggedIn
+
+Emitted by `common`.
+
+_No parameters._
+
+## Page `PhoneLoginPage`
+
+```text
+PhoneLoginPage (page)
+└── LogingButton (widget)
+```
+
+### OnLogin
+
+Emitted by `common/PhoneLoginPage`.
+
+| Parameter | Type | Pattern | Example |
+|---|---|---|---|
+| `passcode` | `String` |  |  |
+| `phone` | `PhoneNumber` → `String` | `^\\+?[0-9]{1,3}-?[0-9]{1,14}$` | `+1-5417543010` |
+
+### OnClick
+
+Emitted by `common/PhoneLoginPage/LogingButton`.
+
+_No parameters._
+"
+        );
+    }
+
+    #[test]
+    fn test_descriptions() {
+        let epath = |path: &str| EPath::from_str(path).unwrap();
+        let mut env = Environment::empty(
+            ProjectIdentifier::new("tuna-bank", "client-app"),
+            Version::from_str("1.0.0").unwrap(),
+        );
+        env.push_component(
+            Component::new(
+                epath("cart"),
+                ComponentType::Module,
+                vec![],
+                vec![epath("cart/CartPage")],
+            )
+            .with_description("Checkout flow."),
+        );
+        env.push_component(
+            Component::new(
+                epath("cart/CartPage"),
+                ComponentType::Page,
+                vec![Event::new(
+                    "OnCheckout",
+                    vec![
+                        EventArgument::new("coupon", std_type("String")),
+                        EventArgument::new("note", std_type("String"))
+                            .with_description("Free text\nof the customer."),
+                    ],
+                )
+                .with_description("Customer started the checkout.")],
+                vec![],
+            )
+            .with_description("Items in the cart."),
+        );
+        let module = env.modules().next().unwrap();
+
+        assert_eq!(
+            MarkdownExporter::of(&env).module_page(module),
+            "# Module `cart`
+
+Checkout flow.
+
+## Page `CartPage`
+
+Items in the cart.
+
+```text
+CartPage (page)
+```
+
+### OnCheckout
+
+Emitted by `cart/CartPage`.
+
+Customer started the checkout.
+
+| Parameter | Type | Pattern | Example | Description |
+|---|---|---|---|---|
+| `coupon` | `ams:std/String` |  |  |  |
+| `note` | `ams:std/String` |  |  | Free text<br>of the customer. |
+"
+        );
+    }
+
+    #[test]
+    fn test_deprecations() {
+        let epath = |path: &str| EPath::from_str(path).unwrap();
+        let deprecation = |replaced_by: &str| {
+            Some(Deprecation::new(
+                Some(Version::from_str("1.2.0").unwrap()),
+                None,
+                Some(replaced_by.to_string()),
+            ))
+        };
+        let mut env = Environment::empty(
+            ProjectIdentifier::new("tuna-bank", "client-app"),
+            Version::from_str("1.0.0").unwrap(),
+        );
+        env.push_component(Component::new(
+            epath("cart"),
+            ComponentType::Module,
+            vec![Event::new(
+                "OnCheckout",
+                vec![
+                    EventArgument::new("code", std_type("String"))
+                        .with_deprecation(deprecation("sku")),
+                    EventArgument::new("sku", std_type("String")),
+                ],
+            )
+            .with_deprecation(deprecation("OnPurchase"))],
+            vec![],
+        ));
+        let module = env.modules().next().unwrap();
+
+        assert!(MarkdownExporter::of(&env).module_page(module).contains(
+            "Emitted by `cart`.
+
+**Deprecated since 1.2.0. Replaced by OnPurchase.**
+
+| Parameter | Type | Pattern | Example | Description |
+|---|---|---|---|---|
+| ~~`code`~~ | `ams:std/String` |  |  | **Deprecated since 1.2.0. Replaced by sku.** |
+| `sku` | `ams:std/String` |  |  |  |
+"
+        ));
+    }
+}
@@ -0,0 +1,28 @@
+mod asyncapi;
+mod avro;
+mod dbt;
+mod ddl;
+mod diagram;
+mod exported_file;
+mod html;
+mod iglu;
+mod json_schema;
+mod markdown;
+pub(crate) mod naming;
+mod protobuf;
+mod segment;
+mod taxonomy;
+
+pub use asyncapi::*;
+pub use avro::*;
+pub use dbt::*;
+pub use ddl::*;
+pub use diagram::*;
+pub use exported_file::*;
+pub use html::*;
+pub use iglu::*;
+pub use json_schema::*;
+pub use markdown::*;
+pub use protobuf::*;
+pub use segment::*;
+pub use taxonomy::*;
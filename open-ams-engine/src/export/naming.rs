@@ -0,0 +1,119 @@
+use std::fmt::Write;
+
+use crate::entity::{Component, Event};
+
+/// Replaces everything outside of `[A-Za-z0-9_]` with `_` and makes sure the result doesn't
+/// start with a digit, which is what most schema languages accept as an identifier.
+pub(crate) fn sanitize_identifier(name: &str) -> String {
+    let mut buf: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if buf.chars().next().is_none_or(|c| c.is_ascii_digit()) {
+        buf.insert(0, '_');
+    }
+    buf
+}
+
+/// Escapes every byte outside of `[A-Za-z0-9]` as `_` followed by its hex value, e.g.
+/// `tuna_2dbank` for `tuna-bank`. Unlike [`sanitize_identifier`], different names always yield
+/// different escapes.
+pub(crate) fn escape_identifier(name: &str) -> String {
+    let mut buf = String::new();
+    for byte in name.bytes() {
+        if byte.is_ascii_alphanumeric() {
+            buf.push(byte as char);
+        } else {
+            write!(buf, "_{:02x}", byte).unwrap();
+        }
+    }
+    buf
+}
+
+/// `PhoneLoginPage` to `phone_login_page`, sanitized with [`sanitize_identifier`].
+pub(crate) fn snake_case(name: &str) -> String {
+    let mut buf = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_ascii_uppercase()
+            && previous.is_some_and(|p| p.is_ascii_lowercase() || p.is_ascii_digit())
+        {
+            buf.push('_');
+        }
+        buf.push(c.to_ascii_lowercase());
+        previous = Some(c);
+    }
+    sanitize_identifier(&buf)
+}
+
+/// `login-method` or `LoginMethod` to `loginMethod`, sanitized with [`sanitize_identifier`].
+pub(crate) fn lower_camel_case(name: &str) -> String {
+    let mut buf = String::new();
+    for (index, word) in snake_case(name)
+        .split('_')
+        .filter(|word| !word.is_empty())
+        .enumerate()
+    {
+        if index == 0 {
+            buf.push_str(word);
+        } else {
+            let mut chars = word.chars();
+            buf.extend(chars.next().map(|first| first.to_ascii_uppercase()));
+            buf.push_str(chars.as_str());
+        }
+    }
+    sanitize_identifier(&buf)
+}
+
+/// `login-method` or `LoginMethod` to `LOGIN_METHOD`, sanitized with [`sanitize_identifier`].
+pub(crate) fn screaming_snake_case(name: &str) -> String {
+    snake_case(name).to_ascii_uppercase()
+}
+
+/// Warehouse table of an event, e.g. `common_phone_login_page_on_login` for `OnLogin` of
+/// `common/PhoneLoginPage`.
+pub(crate) fn event_table_name(component: &Component, event: &Event) -> String {
+    component
+        .epath()
+        .segments
+        .iter()
+        .map(String::as_str)
+        .chain([event.name()])
+        .map(snake_case)
+        .collect::<Vec<String>>()
+        .join("_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_identifier() {
+        assert_eq!(sanitize_identifier("client-app"), "client_app");
+        assert_eq!(sanitize_identifier("1st"), "_1st");
+        assert_eq!(sanitize_identifier(""), "_");
+        assert_eq!(sanitize_identifier("OnLogin"), "OnLogin");
+    }
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("PhoneLoginPage"), "phone_login_page");
+        assert_eq!(snake_case("OnClick2Fa"), "on_click2_fa");
+        assert_eq!(snake_case("client-app"), "client_app");
+    }
+
+    #[test]
+    fn test_lower_camel_case() {
+        assert_eq!(lower_camel_case("LoginMethod"), "loginMethod");
+        assert_eq!(lower_camel_case("date-time"), "dateTime");
+        assert_eq!(lower_camel_case("sms"), "sms");
+        assert_eq!(lower_camel_case("1"), "_1");
+    }
+
+    #[test]
+    fn test_screaming_snake_case() {
+        assert_eq!(screaming_snake_case("biometricFace"), "BIOMETRIC_FACE");
+        assert_eq!(screaming_snake_case("date-time"), "DATE_TIME");
+    }
+}
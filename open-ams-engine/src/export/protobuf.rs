@@ -0,0 +1,475 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::entity::{
+    CollectionKind, Component, Environment, Event, PrimitiveType, TypeDefinitionIdentifier,
+};
+use crate::export::naming::sanitize_identifier;
+use crate::export::{ExportError, ExportedFile};
+
+/// Field numbers 19000 through 19999 are reserved for the protobuf implementation.
+const RESERVED_FIELD_NUMBERS: std::ops::RangeInclusive<u32> = 19000..=19999;
+
+/// Field numbers assigned so far, keyed by fully qualified message name and field name.
+///
+/// Numbers are never removed: once a parameter disappears from the spec its number is emitted
+/// as `reserved` so it can't be reused by a later parameter.
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProtobufFieldNumbers {
+    #[serde(flatten)]
+    messages: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+impl ProtobufFieldNumbers {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, ExportError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(serde_yaml::from_str(&content)?),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        fs::write(path, serde_yaml::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// Returns the number of `field` in `message`, assigning the next free one if it has none.
+    pub fn number_of(&mut self, message: &str, field: &str) -> u32 {
+        let fields = self.messages.entry(message.to_string()).or_default();
+        if let Some(number) = fields.get(field) {
+            return *number;
+        }
+
+        let mut next = fields.values().max().map_or(1, |max| max + 1);
+        if RESERVED_FIELD_NUMBERS.contains(&next) {
+            next = RESERVED_FIELD_NUMBERS.end() + 1;
+        }
+        fields.insert(field.to_string(), next);
+        next
+    }
+
+    /// Numbers assigned to fields of `message` that are not in `present` anymore.
+    pub fn retired(&self, message: &str, present: &[&str]) -> Vec<u32> {
+        self.messages
+            .get(message)
+            .map(|fields| {
+                let mut buf: Vec<u32> = fields
+                    .iter()
+                    .filter(|(name, _)| !present.contains(&name.as_str()))
+                    .map(|(_, number)| *number)
+                    .collect();
+                buf.sort();
+                buf
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Exports one `.proto` file per module holding a message for every event of the module tree.
+#[derive(Debug)]
+pub struct ProtobufExporter<'env> {
+    env: &'env Environment,
+}
+
+impl<'env> ProtobufExporter<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        ProtobufExporter { env }
+    }
+
+    /// Renders the modules, assigning numbers to new fields in `field_numbers`. The caller is
+    /// responsible for persisting `field_numbers` afterwards.
+    pub fn export(
+        &self,
+        field_numbers: &mut ProtobufFieldNumbers,
+    ) -> Result<Vec<ExportedFile>, ExportError> {
+        let mut buf = Vec::new();
+        for module in self.env.modules() {
+            let events: Vec<(&Component, &Event)> = self
+                .env
+                .component_tree(module)
+                .into_iter()
+                .flat_map(|component| {
+                    component
+                        .events()
+                        .iter()
+                        .map(move |event| (component, event))
+                })
+                .collect();
+            if events.is_empty() {
+                continue;
+            }
+
+            let package = self.package(module);
+            let mut content = String::new();
+            writeln!(
+                content,
+                "// Generated from {}. Do not edit.",
+                self.env.identifier()
+            )
+            .unwrap();
+            writeln!(content, "syntax = \"proto3\";\n").unwrap();
+            writeln!(content, "package {};", package).unwrap();
+
+            let mut objects = Vec::new();
+            for (component, event) in events {
+                let name = message_name(module, component, event);
+                let fields: Vec<Field> = event
+                    .arguments()
+                    .iter()
+                    .map(|argument| (argument.name(), argument.type_ref(), argument.required()))
+                    .collect();
+                writeln!(content).unwrap();
+                writeln!(
+                    content,
+                    "// {}/{}",
+                    component.epath().to_string(),
+                    event.name()
+                )
+                .unwrap();
+                self.write_message(
+                    &mut content,
+                    field_numbers,
+                    &package,
+                    &name,
+                    &fields,
+                    &mut objects,
+                )?;
+            }
+            // Messages of object types, appended as they are discovered so that objects
+            // referencing other objects, or themselves, are written once.
+            let mut written = 0;
+            while let Some(identifier) = objects.get(written).cloned() {
+                written += 1;
+                let Some(object_definition) = self.env.object_definition(&identifier) else {
+                    continue;
+                };
+                let fields: Vec<Field> = object_definition
+                    .fields()
+                    .iter()
+                    .map(|field| (field.name(), field.type_ref(), field.required()))
+                    .collect();
+                writeln!(content).unwrap();
+                writeln!(content, "// {}", identifier).unwrap();
+                self.write_message(
+                    &mut content,
+                    field_numbers,
+                    &package,
+                    &object_message_name(&identifier),
+                    &fields,
+                    &mut objects,
+                )?;
+            }
+
+            let mut path: PathBuf = package.split('.').collect();
+            path.set_extension("proto");
+            buf.push(ExportedFile::new(path, content));
+        }
+        Ok(buf)
+    }
+
+    /// Package made of the project group and name followed by the module path,
+    /// e.g. `tuna_bank.client_app.common`.
+    pub fn package(&self, module: &Component) -> String {
+        let identifier = self.env.identifier();
+        [identifier.group(), identifier.name()]
+            .into_iter()
+            .chain(module.epath().segments.iter().map(String::as_str))
+            .map(|segment| sanitize_identifier(&segment.to_lowercase()))
+            .collect::<Vec<String>>()
+            .join(".")
+    }
+
+    /// Writes message `name` of `package`, adding the object types its fields refer to and that
+    /// are not in `objects` yet to `objects`.
+    fn write_message(
+        &self,
+        out: &mut String,
+        field_numbers: &mut ProtobufFieldNumbers,
+        package: &str,
+        name: &str,
+        fields: &[Field],
+        objects: &mut Vec<TypeDefinitionIdentifier>,
+    ) -> Result<(), ExportError> {
+        let full_name = format!("{}.{}", package, name);
+        writeln!(out, "message {} {{", name).unwrap();
+
+        let present: Vec<&str> = fields.iter().map(|(name, _, _)| *name).collect();
+        let retired = field_numbers.retired(&full_name, &present);
+        if !retired.is_empty() {
+            let numbers: Vec<String> = retired.iter().map(u32::to_string).collect();
+            writeln!(out, "  reserved {};", numbers.join(", ")).unwrap();
+        }
+
+        for (field_name, type_ref, required) in fields {
+            writeln!(
+                out,
+                "  {} {} = {};",
+                self.field_type(type_ref, *required, objects)?,
+                sanitize_identifier(field_name),
+                field_numbers.number_of(&full_name, field_name),
+            )
+            .unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        Ok(())
+    }
+
+    /// `repeated T` for lists and `map<string, T>` for maps, which can't be marked `optional` and
+    /// are simply empty when left out, otherwise the element type, `optional` unless `required`.
+    fn field_type(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+        required: bool,
+        objects: &mut Vec<TypeDefinitionIdentifier>,
+    ) -> Result<String, ExportError> {
+        match self.env.collection_definition(identifier) {
+            Some(collection) => {
+                let element = self.element_type(collection.element(), objects)?;
+                Ok(match collection.kind() {
+                    CollectionKind::List => format!("repeated {}", element),
+                    CollectionKind::Map => format!("map<string, {}>", element),
+                })
+            }
+            None if required => self.element_type(identifier, objects),
+            None => Ok(format!(
+                "optional {}",
+                self.element_type(identifier, objects)?
+            )),
+        }
+    }
+
+    /// Scalar type or message name of `identifier`. Protobuf doesn't support collections of
+    /// collections, so those are rejected.
+    fn element_type(
+        &self,
+        identifier: &TypeDefinitionIdentifier,
+        objects: &mut Vec<TypeDefinitionIdentifier>,
+    ) -> Result<String, ExportError> {
+        if self.env.object_definition(identifier).is_some() {
+            if !objects.contains(identifier) {
+                objects.push(identifier.clone());
+            }
+            return Ok(object_message_name(identifier));
+        }
+        match self.env.primitive_type(identifier) {
+            Some(primitive_type) => Ok(proto_type(primitive_type).to_string()),
+            None => Err(ExportError::UnsupportedType {
+                type_ref: identifier.clone(),
+                format: "Protobuf",
+            }),
+        }
+    }
+}
+
+/// Name, type and whether it is required, of an event parameter or object field.
+type Field<'a> = (&'a str, &'a TypeDefinitionIdentifier, bool);
+
+fn proto_type(primitive_type: PrimitiveType) -> &'static str {
+    match primitive_type {
+        PrimitiveType::String => "string",
+        PrimitiveType::Integer => "int32",
+        PrimitiveType::Long => "int64",
+        PrimitiveType::Float => "double",
+        PrimitiveType::Boolean => "bool",
+    }
+}
+
+/// Capitalized path segments of an object type, e.g. `CatalogNode` for `catalog/Node`.
+fn object_message_name(identifier: &TypeDefinitionIdentifier) -> String {
+    let name: String = identifier
+        .path()
+        .segments
+        .iter()
+        .map(|segment| {
+            let mut chars = segment.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+    sanitize_identifier(&name)
+}
+
+/// Component path below the module followed by the event name, e.g. `PhoneLoginPageOnLogin`.
+fn message_name(module: &Component, component: &Component, event: &Event) -> String {
+    let relative = &component.epath().segments[module.epath().segments.len()..];
+    let name: String = relative
+        .iter()
+        .map(String::as_str)
+        .chain([event.name()])
+        .collect();
+    sanitize_identifier(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{
+        ComponentType, EventArgument, ObjectField, TypeConstraints, TypeDefinition,
+    };
+    use crate::source::entity::EPath;
+    use crate::util::{example_environment, std_type, type_identifier};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_export_module() {
+        let env = example_environment();
+        let mut field_numbers = ProtobufFieldNumbers::default();
+        let files = ProtobufExporter::of(&env)
+            .export(&mut field_numbers)
+            .unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(
+            files[0].path(),
+            Path::new("tuna_bank/client_app/common.proto")
+        );
+        assert_eq!(
+            files[0].content(),
+            "// Generated from tuna-bank:client-app. Do not edit.
+syntax = \"proto3\";
+
+package tuna_bank.client_app.common;
+
+// common/UserLoggedIn
+message UserLoggedIn {
+}
+
+// common/PhoneLoginPage/OnLogin
+message PhoneLoginPageOnLogin {
+  string passcode = 1;
+  string phone = 2;
+}
+
+// common/PhoneLoginPage/LogingButton/OnClick
+message PhoneLoginPageLogingButtonOnClick {
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_object_and_collection_fields() {
+        let mut env = example_environment();
+        let string = std_type("String");
+        let node = type_identifier("tuna-bank:client-app", "catalog/Node");
+        let children = TypeDefinitionIdentifier::collection(CollectionKind::List, &node);
+        let labels = TypeDefinitionIdentifier::collection(CollectionKind::Map, &string);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            node.clone(),
+        ));
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::Map,
+            string.clone(),
+        ));
+        env.push_type_definition(TypeDefinition::new_object(
+            node.clone(),
+            vec![
+                ObjectField::new("name", string),
+                ObjectField::new_optional("parent", node.clone(), None),
+                ObjectField::new("children", children.clone()),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        env.push_component(Component::new(
+            EPath::from_str("catalog").unwrap(),
+            ComponentType::Module,
+            vec![Event::new(
+                "OnBrowse",
+                vec![
+                    EventArgument::new("node", node),
+                    EventArgument::new_optional("path", children, None),
+                    EventArgument::new("labels", labels),
+                ],
+            )],
+            vec![],
+        ));
+        let files = ProtobufExporter::of(&env)
+            .export(&mut ProtobufFieldNumbers::default())
+            .unwrap();
+        let catalog = files
+            .iter()
+            .find(|file| file.path() == Path::new("tuna_bank/client_app/catalog.proto"))
+            .unwrap();
+
+        assert_eq!(
+            catalog.content(),
+            "// Generated from tuna-bank:client-app. Do not edit.
+syntax = \"proto3\";
+
+package tuna_bank.client_app.catalog;
+
+// catalog/OnBrowse
+message OnBrowse {
+  CatalogNode node = 1;
+  repeated CatalogNode path = 2;
+  map<string, string> labels = 3;
+}
+
+// tuna-bank:client-app/catalog/Node
+message CatalogNode {
+  string name = 1;
+  optional CatalogNode parent = 2;
+  repeated CatalogNode children = 3;
+}
+"
+        );
+    }
+
+    #[test]
+    fn test_nested_collection_is_unsupported() {
+        let mut env = example_environment();
+        let string = std_type("String");
+        let list = TypeDefinitionIdentifier::collection(CollectionKind::List, &string);
+        let matrix = TypeDefinitionIdentifier::collection(CollectionKind::List, &list);
+        env.push_type_definition(TypeDefinition::new_collection(CollectionKind::List, string));
+        env.push_type_definition(TypeDefinition::new_collection(CollectionKind::List, list));
+        env.push_component(Component::new(
+            EPath::from_str("grid").unwrap(),
+            ComponentType::Module,
+            vec![Event::new(
+                "OnSelect",
+                vec![EventArgument::new("cells", matrix)],
+            )],
+            vec![],
+        ));
+
+        assert!(matches!(
+            ProtobufExporter::of(&env).export(&mut ProtobufFieldNumbers::default()),
+            Err(ExportError::UnsupportedType {
+                format: "Protobuf",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_field_numbers_are_stable() {
+        let mut field_numbers: ProtobufFieldNumbers =
+            serde_yaml::from_str("pkg.Message:\n  phone: 1\n  legacy: 2\n").unwrap();
+
+        assert_eq!(field_numbers.number_of("pkg.Message", "zip"), 3);
+        assert_eq!(field_numbers.number_of("pkg.Message", "phone"), 1);
+        assert_eq!(
+            field_numbers.retired("pkg.Message", &["phone", "zip"]),
+            vec![2]
+        );
+    }
+
+    #[test]
+    fn test_field_numbers_skip_reserved_range() {
+        let mut field_numbers: ProtobufFieldNumbers =
+            serde_yaml::from_str("pkg.Message:\n  a: 18999\n").unwrap();
+
+        assert_eq!(field_numbers.number_of("pkg.Message", "b"), 20000);
+    }
+}
@@ -0,0 +1,216 @@
+use std::str::FromStr;
+
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::entity::{Component, Environment, Event};
+use crate::export::naming::event_table_name;
+use crate::export::{ExportError, ExportedFile, JsonSchemaExporter};
+
+/// Dialect of the rules Segment Protocols accepts in a tracking plan.
+const SEGMENT_SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// How the name of a tracked event is derived from its component path and event name, e.g. for
+/// `OnLogin` of `common/PhoneLoginPage`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EventNamingStrategy {
+    /// `OnLogin`
+    Name,
+    /// `common/PhoneLoginPage/OnLogin`
+    Path,
+    /// `common_phone_login_page_on_login`
+    SnakeCase,
+    /// `Common Phone Login Page On Login`
+    #[default]
+    TitleCase,
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Unknown event naming strategy '{0}', expected one of: name, path, snake_case, title_case")]
+pub struct UnknownEventNamingStrategy(String);
+
+impl FromStr for EventNamingStrategy {
+    type Err = UnknownEventNamingStrategy;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('-', "_").as_str() {
+            "name" => Ok(EventNamingStrategy::Name),
+            "path" => Ok(EventNamingStrategy::Path),
+            "snake_case" | "snake" => Ok(EventNamingStrategy::SnakeCase),
+            "title_case" | "title" => Ok(EventNamingStrategy::TitleCase),
+            _ => Err(UnknownEventNamingStrategy(s.to_string())),
+        }
+    }
+}
+
+impl EventNamingStrategy {
+    pub fn event_name(&self, component: &Component, event: &Event) -> String {
+        match self {
+            EventNamingStrategy::Name => event.name().to_string(),
+            EventNamingStrategy::Path => component
+                .epath()
+                .segments
+                .iter()
+                .map(String::as_str)
+                .chain([event.name()])
+                .collect::<Vec<&str>>()
+                .join("/"),
+            EventNamingStrategy::SnakeCase => event_table_name(component, event),
+            EventNamingStrategy::TitleCase => event_table_name(component, event)
+                .split('_')
+                .filter(|word| !word.is_empty())
+                .map(capitalized)
+                .collect::<Vec<String>>()
+                .join(" "),
+        }
+    }
+}
+
+fn capitalized(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Exports a Segment Protocols tracking plan with an event per resolved event, whose rules
+/// validate the `properties` of a track call.
+#[derive(Debug)]
+pub struct SegmentExporter<'env> {
+    env: &'env Environment,
+    naming: EventNamingStrategy,
+}
+
+impl<'env> SegmentExporter<'env> {
+    pub fn of(env: &'env Environment, naming: EventNamingStrategy) -> Self {
+        SegmentExporter { env, naming }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        Ok(vec![ExportedFile::new(
+            "tracking-plan.json",
+            serde_json::to_string_pretty(&self.tracking_plan())?,
+        )])
+    }
+
+    pub fn tracking_plan(&self) -> Value {
+        let events: Vec<Value> = self
+            .env
+            .events()
+            .map(|(component, event)| self.event(component, event))
+            .collect();
+
+        json!({
+            "display_name": self.env.identifier().to_string(),
+            "rules": {
+                "events": events,
+            },
+        })
+    }
+
+    fn event(&self, component: &Component, event: &Event) -> Value {
+        let properties =
+            JsonSchemaExporter::of(self.env).event_schema_at(event, "#/properties/properties");
+        let description = match event.description().trim() {
+            "" => format!(
+                "{} emitted by {}",
+                event.name(),
+                component.epath().to_string()
+            ),
+            declared => declared.to_string(),
+        };
+
+        json!({
+            "name": self.naming.event_name(component, event),
+            "description": description,
+            "version": 1,
+            "rules": {
+                "$schema": SEGMENT_SCHEMA_DIALECT,
+                "type": "object",
+                "properties": {
+                    "properties": properties,
+                },
+                "required": ["properties"],
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::ComponentType;
+    use crate::source::entity::EPath;
+    use crate::util::example_environment;
+
+    #[test]
+    fn test_naming_strategies() {
+        let env = example_environment();
+        let (component, event) = env.events().find(|(_, e)| e.name() == "OnLogin").unwrap();
+
+        let names: Vec<String> = [
+            EventNamingStrategy::Name,
+            EventNamingStrategy::Path,
+            EventNamingStrategy::SnakeCase,
+            EventNamingStrategy::TitleCase,
+        ]
+        .iter()
+        .map(|naming| naming.event_name(component, event))
+        .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "OnLogin",
+                "common/PhoneLoginPage/OnLogin",
+                "common_phone_login_page_on_login",
+                "Common Phone Login Page On Login",
+            ]
+        );
+        assert_eq!("snake-case".parse(), Ok(EventNamingStrategy::SnakeCase));
+        assert!("kebab".parse::<EventNamingStrategy>().is_err());
+    }
+
+    #[test]
+    fn test_tracking_plan() {
+        let env = example_environment();
+        let plan = SegmentExporter::of(&env, EventNamingStrategy::Path).tracking_plan();
+        let event = &plan["rules"]["events"][1];
+
+        assert_eq!(plan["display_name"], "tuna-bank:client-app");
+        assert_eq!(plan["rules"]["events"].as_array().unwrap().len(), 3);
+        assert_eq!(event["name"], "common/PhoneLoginPage/OnLogin");
+        assert_eq!(event["rules"]["required"], json!(["properties"]));
+
+        let properties = &event["rules"]["properties"]["properties"];
+        assert_eq!(properties["required"], json!(["passcode", "phone"]));
+        assert_eq!(
+            properties["properties"]["phone"]["pattern"],
+            "^\\+?[0-9]{1,3}-?[0-9]{1,14}$"
+        );
+    }
+
+    #[test]
+    fn test_declared_description() {
+        let mut env = example_environment();
+        env.push_component(Component::new(
+            EPath::from_str("search").unwrap(),
+            ComponentType::Module,
+            vec![Event::new("OnSearch", vec![])
+                .with_description("Search submitted from the toolbar\n")],
+            vec![],
+        ));
+        let plan = SegmentExporter::of(&env, EventNamingStrategy::Path).tracking_plan();
+        let events = plan["rules"]["events"].as_array().unwrap();
+
+        assert_eq!(
+            events[1]["description"],
+            "OnLogin emitted by common/PhoneLoginPage"
+        );
+        assert_eq!(
+            events.last().unwrap()["description"],
+            "Search submitted from the toolbar"
+        );
+    }
+}
@@ -0,0 +1,331 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::entity::{Component, ComponentType, Environment, Event, EventArgument, PrimitiveType};
+use crate::export::{EventNamingStrategy, ExportError, ExportedFile};
+use crate::source::entity::EPath;
+
+/// Product analytics tool the taxonomy CSV is laid out for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaxonomyFormat {
+    Amplitude,
+    Mixpanel,
+}
+
+#[derive(Debug, Error, PartialEq)]
+#[error("Unknown taxonomy format '{0}', expected one of: amplitude, mixpanel")]
+pub struct UnknownTaxonomyFormat(String);
+
+impl FromStr for TaxonomyFormat {
+    type Err = UnknownTaxonomyFormat;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "amplitude" => Ok(TaxonomyFormat::Amplitude),
+            "mixpanel" => Ok(TaxonomyFormat::Mixpanel),
+            _ => Err(UnknownTaxonomyFormat(s.to_string())),
+        }
+    }
+}
+
+impl TaxonomyFormat {
+    fn file_name(&self) -> &'static str {
+        match self {
+            TaxonomyFormat::Amplitude => "amplitude-taxonomy.csv",
+            TaxonomyFormat::Mixpanel => "mixpanel-lexicon.csv",
+        }
+    }
+
+    fn header(&self) -> [&'static str; 8] {
+        match self {
+            TaxonomyFormat::Amplitude => [
+                "Event Type",
+                "Event Description",
+                "Event Category",
+                "Property Name",
+                "Property Description",
+                "Property Value Type",
+                "Property Regex",
+                "Property Required",
+            ],
+            TaxonomyFormat::Mixpanel => [
+                "Entity Type",
+                "Entity Name",
+                "Entity Description",
+                "Entity Category",
+                "Property Name",
+                "Property Description",
+                "Property Type",
+                "Property Regex",
+            ],
+        }
+    }
+
+    fn property_type(&self, primitive_type: Option<PrimitiveType>) -> &'static str {
+        match (self, primitive_type) {
+            (TaxonomyFormat::Amplitude, Some(PrimitiveType::Integer | PrimitiveType::Long)) => {
+                "integer"
+            }
+            (TaxonomyFormat::Amplitude, Some(PrimitiveType::Float)) => "number",
+            (TaxonomyFormat::Amplitude, Some(PrimitiveType::Boolean)) => "boolean",
+            (TaxonomyFormat::Amplitude, _) => "string",
+            (
+                TaxonomyFormat::Mixpanel,
+                Some(PrimitiveType::Integer | PrimitiveType::Long | PrimitiveType::Float),
+            ) => "Numeric",
+            (TaxonomyFormat::Mixpanel, Some(PrimitiveType::Boolean)) => "Boolean",
+            (TaxonomyFormat::Mixpanel, _) => "String",
+        }
+    }
+}
+
+/// Exports the event taxonomy as a CSV with a row per event parameter, or a single row for events
+/// without parameters, ready to be imported into Amplitude Data or Mixpanel Lexicon.
+#[derive(Debug)]
+pub struct TaxonomyExporter<'env> {
+    env: &'env Environment,
+    format: TaxonomyFormat,
+    naming: EventNamingStrategy,
+}
+
+impl<'env> TaxonomyExporter<'env> {
+    pub fn of(env: &'env Environment, format: TaxonomyFormat, naming: EventNamingStrategy) -> Self {
+        TaxonomyExporter {
+            env,
+            format,
+            naming,
+        }
+    }
+
+    pub fn export(&self) -> Result<Vec<ExportedFile>, ExportError> {
+        Ok(vec![ExportedFile::new(
+            self.format.file_name(),
+            self.taxonomy()?,
+        )])
+    }
+
+    pub fn taxonomy(&self) -> Result<String, ExportError> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(self.format.header())?;
+        for (component, event) in self.env.events() {
+            if event.arguments().is_empty() {
+                writer.write_record(self.row(component, event, None))?;
+            }
+            for argument in event.arguments() {
+                writer.write_record(self.row(component, event, Some(argument)))?;
+            }
+        }
+
+        let buf = writer.into_inner().map_err(|e| e.into_error())?;
+        Ok(String::from_utf8(buf).expect("CSV is written from UTF-8 strings"))
+    }
+
+    fn row(
+        &self,
+        component: &Component,
+        event: &Event,
+        argument: Option<&EventArgument>,
+    ) -> Vec<String> {
+        let name = self.naming.event_name(component, event);
+        let description = match event.description().trim() {
+            "" => format!(
+                "{} emitted by {}",
+                event.name(),
+                component.epath().to_string()
+            ),
+            declared => declared.to_string(),
+        };
+        let category = self.category(component);
+
+        let (property, property_description, property_type, regex, required) = match argument {
+            Some(argument) => {
+                let type_ref = argument.type_ref();
+                (
+                    argument.name().to_string(),
+                    match argument.description().trim() {
+                        "" => format!("Parameter of type {}", type_ref),
+                        declared => declared.to_string(),
+                    },
+                    self.format
+                        .property_type(self.env.primitive_type(type_ref))
+                        .to_string(),
+                    combined_pattern(&self.env.patterns(type_ref)),
+                    argument.required().to_string(),
+                )
+            }
+            None => Default::default(),
+        };
+
+        match self.format {
+            TaxonomyFormat::Amplitude => vec![
+                name,
+                description,
+                category,
+                property,
+                property_description,
+                property_type,
+                regex,
+                required,
+            ],
+            TaxonomyFormat::Mixpanel => vec![
+                "Event".to_string(),
+                name,
+                description,
+                category,
+                property,
+                property_description,
+                property_type,
+                regex,
+            ],
+        }
+    }
+
+    /// Name of the page the component belongs to, falling back to the path of its module for
+    /// events that are not emitted from within a page.
+    fn category(&self, component: &Component) -> String {
+        let segments = &component.epath().segments;
+        let mut module = None;
+        for length in (0..=segments.len()).rev() {
+            let prefix = EPath::new(segments[..length].to_vec());
+            let Some(ancestor) = self.env.component(&prefix) else {
+                continue;
+            };
+            match ancestor.component_type() {
+                ComponentType::Page => return segments[length - 1].clone(),
+                ComponentType::Module if module.is_none() => module = Some(prefix.to_string()),
+                _ => {}
+            }
+        }
+        module.unwrap_or_default()
+    }
+}
+
+/// Single regular expression requiring all of `patterns` to match, as taxonomy tools accept only
+/// one regex per property. Each pattern is a lookahead that may match anywhere in the value,
+/// unanchored patterns matching anywhere just like on their own.
+fn combined_pattern(patterns: &[&str]) -> String {
+    match patterns {
+        [] => String::new(),
+        [pattern] => pattern.to_string(),
+        _ => patterns
+            .iter()
+            .map(|pattern| format!("(?=.*(?:{}))", pattern))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::{example_environment, std_type};
+
+    #[test]
+    fn test_amplitude() {
+        let env = example_environment();
+        let taxonomy = TaxonomyExporter::of(
+            &env,
+            TaxonomyFormat::Amplitude,
+            EventNamingStrategy::TitleCase,
+        )
+        .taxonomy()
+        .unwrap();
+
+        assert_eq!(
+            taxonomy,
+            "Event Type,Event Description,Event Category,Property Name,Property Description,Property Value Type,Property Regex,Property Required
+Common User Logged In,UserLoggedIn emitted by common,common,,,,,
+Common Phone Login Page On Login,OnLogin emitted by common/PhoneLoginPage,PhoneLoginPage,passcode,Parameter of type ams:std/String,string,,true
+Common Phone Login Page On Login,OnLogin emitted by common/PhoneLoginPage,PhoneLoginPage,phone,Parameter of type tuna-bank:client-app/common/PhoneNumber,string,\"^\\+?[0-9]{1,3}-?[0-9]{1,14}$\",true
+Common Phone Login Page Loging Button On Click,OnClick emitted by common/PhoneLoginPage/LogingButton,PhoneLoginPage,,,,,
+"
+        );
+    }
+
+    #[test]
+    fn test_amplitude_optional_parameter() {
+        let mut env = example_environment();
+        env.push_component(Component::new(
+            EPath::from_str("search").unwrap(),
+            ComponentType::Module,
+            vec![Event::new(
+                "OnSearch",
+                vec![
+                    EventArgument::new("query", std_type("String")),
+                    EventArgument::new_optional("referrer", std_type("String"), None),
+                ],
+            )],
+            vec![],
+        ));
+        let taxonomy = TaxonomyExporter::of(
+            &env,
+            TaxonomyFormat::Amplitude,
+            EventNamingStrategy::TitleCase,
+        )
+        .taxonomy()
+        .unwrap();
+
+        assert!(taxonomy.contains(
+            "Search On Search,OnSearch emitted by search,search,query,Parameter of type ams:std/String,string,,true
+Search On Search,OnSearch emitted by search,search,referrer,Parameter of type ams:std/String,string,,false
+"
+        ));
+    }
+
+    #[test]
+    fn test_declared_descriptions() {
+        let mut env = example_environment();
+        env.push_component(Component::new(
+            EPath::from_str("search").unwrap(),
+            ComponentType::Module,
+            vec![Event::new(
+                "OnSearch",
+                vec![
+                    EventArgument::new("query", std_type("String"))
+                        .with_description("Text typed by the user\n"),
+                    EventArgument::new("locale", std_type("String")),
+                ],
+            )
+            .with_description("Search submitted from the toolbar\n")],
+            vec![],
+        ));
+        let taxonomy =
+            TaxonomyExporter::of(&env, TaxonomyFormat::Mixpanel, EventNamingStrategy::Path)
+                .taxonomy()
+                .unwrap();
+
+        assert!(taxonomy.contains(
+            "Event,search/OnSearch,Search submitted from the toolbar,search,query,Text typed by the user,String,
+Event,search/OnSearch,Search submitted from the toolbar,search,locale,Parameter of type ams:std/String,String,
+"
+        ));
+    }
+
+    #[test]
+    fn test_mixpanel() {
+        let env = example_environment();
+        let taxonomy =
+            TaxonomyExporter::of(&env, TaxonomyFormat::Mixpanel, EventNamingStrategy::Path)
+                .taxonomy()
+                .unwrap();
+        let lines: Vec<&str> = taxonomy.lines().collect();
+
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("Entity Type,Entity Name,"));
+        assert_eq!(
+            lines[2],
+            "Event,common/PhoneLoginPage/OnLogin,OnLogin emitted by common/PhoneLoginPage,PhoneLoginPage,passcode,Parameter of type ams:std/String,String,"
+        );
+    }
+
+    #[test]
+    fn test_combined_pattern() {
+        assert_eq!(combined_pattern(&[]), "");
+        assert_eq!(combined_pattern(&["^a"]), "^a");
+        assert_eq!(combined_pattern(&["^a", "b$"]), "(?=.*(?:^a))(?=.*(?:b$))");
+        assert_eq!(
+            combined_pattern(&["[0-9]", "x|y"]),
+            "(?=.*(?:[0-9]))(?=.*(?:x|y))"
+        );
+    }
+}
@@ -0,0 +1,613 @@
+use std::collections::HashMap;
+
+use rand::distributions::{Alphanumeric, DistString};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+
+use crate::entity::{
+    CollectionKind, CollectionTypeDefinition, Component, Environment, Event, PrimitiveType,
+    StringFormat, TypeConstraints, TypeDefinitionIdentifier,
+};
+use crate::source::entity::EPath;
+
+/// Upper bound of repetitions like `*` and `+` when generating strings from a pattern.
+const MAX_PATTERN_REPEAT: u32 = 16;
+/// Attempts to generate a string matching every pattern of an alias chain before giving up.
+const MAX_PATTERN_ATTEMPTS: usize = 32;
+
+/// Width of the range numbers are picked from when a type declares at most one bound.
+const NUMBER_SPAN: f64 = 1000.0;
+const STRING_LENGTH: std::ops::RangeInclusive<u64> = 8..=16;
+/// Number of elements of generated lists and entries of generated maps.
+const COLLECTION_SIZE: std::ops::RangeInclusive<usize> = 1..=3;
+/// Length of generated map keys.
+const MAP_KEY_LENGTH: usize = 6;
+/// Chance of leaving an optional parameter or field out of a payload.
+const OMITTED_PROBABILITY: f64 = 0.25;
+/// Nesting of objects and collections past which collections are left empty and optional
+/// fields out, so that recursive objects yield finite values.
+const MAX_DEPTH: usize = 8;
+
+/// Generates synthetic event payloads for load tests and seeding dashboards.
+///
+/// Objects get a value for each of their fields, lists and maps a few random elements. Optional
+/// parameters and fields are sometimes left out. Other parameters get the `example` of their type
+/// when there is one, otherwise one of its enum values, a string generated
+/// from the `pattern` or `format` of the type, or a random number within its constraints. The
+/// same seed always yields the same sequence of payloads.
+#[derive(Debug)]
+pub struct PayloadGenerator<'env> {
+    env: &'env Environment,
+    rng: StdRng,
+    generators: HashMap<&'env str, Option<rand_regex::Regex>>,
+    matchers: HashMap<&'env str, Option<regex::Regex>>,
+}
+
+impl<'env> PayloadGenerator<'env> {
+    pub fn of(env: &'env Environment, seed: u64) -> Self {
+        let patterns: Vec<&str> = env
+            .type_definitions()
+            .iter()
+            .filter_map(|definition| definition.pattern())
+            .collect();
+
+        PayloadGenerator {
+            env,
+            rng: StdRng::seed_from_u64(seed),
+            generators: patterns
+                .iter()
+                .map(|pattern| {
+                    (
+                        *pattern,
+                        rand_regex::Regex::compile(unanchored(pattern), MAX_PATTERN_REPEAT).ok(),
+                    )
+                })
+                .collect(),
+            matchers: patterns
+                .iter()
+                .map(|pattern| (*pattern, regex::Regex::new(pattern).ok()))
+                .collect(),
+        }
+    }
+
+    /// `count` tracked events in the envelope accepted by
+    /// [`TrackedEvent`](crate::validator::TrackedEvent), picked uniformly from all events of the
+    /// environment or only the event at `only`.
+    pub fn generate(
+        &mut self,
+        count: usize,
+        only: Option<&EPath>,
+    ) -> Result<Vec<Value>, GeneratorError> {
+        let events: Vec<(&'env Component, &'env Event)> = match only {
+            Some(path) => vec![self
+                .env
+                .event(path)
+                .ok_or_else(|| GeneratorError::UnknownEvent(path.clone()))?],
+            None => self.env.events().collect(),
+        };
+        if events.is_empty() {
+            return Err(GeneratorError::NoEvents);
+        }
+
+        let mut buf = Vec::with_capacity(count);
+        for _ in 0..count {
+            let (component, event) = events[self.rng.gen_range(0..events.len())];
+            buf.push(json!({
+                "event": component.epath().extended(event.name()),
+                "properties": self.payload(event),
+            }));
+        }
+        Ok(buf)
+    }
+
+    pub fn payload(&mut self, event: &Event) -> Value {
+        let mut properties = Map::new();
+        for argument in event.arguments() {
+            if !argument.required() && self.rng.gen_bool(OMITTED_PROBABILITY) {
+                continue;
+            }
+            properties.insert(argument.name().to_string(), self.value(argument.type_ref()));
+        }
+        Value::Object(properties)
+    }
+
+    pub fn value(&mut self, identifier: &TypeDefinitionIdentifier) -> Value {
+        self.nested_value(identifier, 0)
+    }
+
+    /// Value of a type nested `depth` objects and collections deep.
+    fn nested_value(&mut self, identifier: &TypeDefinitionIdentifier, depth: usize) -> Value {
+        if let Some(object_definition) = self.env.object_definition(identifier) {
+            let mut fields = Map::new();
+            for field in object_definition.fields() {
+                if !field.required()
+                    && (depth >= MAX_DEPTH || self.rng.gen_bool(OMITTED_PROBABILITY))
+                {
+                    continue;
+                }
+                let value = self.nested_value(field.type_ref(), depth + 1);
+                fields.insert(field.name().to_string(), value);
+            }
+            return Value::Object(fields);
+        }
+        if let Some(collection) = self.env.collection_definition(identifier) {
+            return self.collection(collection, depth);
+        }
+
+        let primitive_type = self.env.primitive_type(identifier);
+        if let Some(example) = self.env.example(identifier) {
+            return typed_example(primitive_type, example);
+        }
+        if let Some(enum_definition) = self.env.enum_definition(identifier) {
+            let values = enum_definition.values();
+            if !values.is_empty() {
+                let value = &values[self.rng.gen_range(0..values.len())];
+                return typed_example(primitive_type, value.value());
+            }
+        }
+
+        let constraints = self.env.constraints(identifier);
+        match primitive_type {
+            Some(PrimitiveType::Integer | PrimitiveType::Long) => {
+                json!(self.integer(&constraints))
+            }
+            Some(PrimitiveType::Float) => json!(self.float(&constraints)),
+            Some(PrimitiveType::Boolean) => json!(self.rng.gen::<bool>()),
+            Some(PrimitiveType::String) | None => json!(self.string(identifier, &constraints)),
+        }
+    }
+
+    fn collection(&mut self, collection: &CollectionTypeDefinition, depth: usize) -> Value {
+        let size = match depth >= MAX_DEPTH {
+            true => 0,
+            false => self.rng.gen_range(COLLECTION_SIZE),
+        };
+        match collection.kind() {
+            CollectionKind::List => Value::Array(
+                (0..size)
+                    .map(|_| self.nested_value(collection.element(), depth + 1))
+                    .collect(),
+            ),
+            CollectionKind::Map => {
+                let mut entries = Map::new();
+                for _ in 0..size {
+                    let key = Alphanumeric.sample_string(&mut self.rng, MAP_KEY_LENGTH);
+                    let value = self.nested_value(collection.element(), depth + 1);
+                    entries.insert(key, value);
+                }
+                Value::Object(entries)
+            }
+        }
+    }
+
+    fn integer(&mut self, constraints: &TypeConstraints) -> i64 {
+        let (minimum, maximum) = number_range(constraints);
+        match constraints
+            .multiple_of
+            .filter(|multiple_of| *multiple_of > 0.0)
+        {
+            Some(multiple_of) => self.multiple(multiple_of, minimum, maximum).round() as i64,
+            None => {
+                let minimum = minimum.ceil() as i64;
+                let maximum = (maximum.floor() as i64).max(minimum);
+                self.rng.gen_range(minimum..=maximum)
+            }
+        }
+    }
+
+    fn float(&mut self, constraints: &TypeConstraints) -> f64 {
+        let (minimum, maximum) = number_range(constraints);
+        match constraints
+            .multiple_of
+            .filter(|multiple_of| *multiple_of > 0.0)
+        {
+            Some(multiple_of) => self.multiple(multiple_of, minimum, maximum),
+            None => {
+                let value: f64 = self.rng.gen_range(minimum..=maximum.max(minimum));
+                let rounded = (value * 100.0).round() / 100.0;
+                rounded.clamp(minimum, maximum.max(minimum))
+            }
+        }
+    }
+
+    /// Random multiple of `multiple_of` between `minimum` and `maximum`, or the multiple closest
+    /// to `minimum` when the range holds none.
+    fn multiple(&mut self, multiple_of: f64, minimum: f64, maximum: f64) -> f64 {
+        let first = (minimum / multiple_of).ceil() as i64;
+        let last = ((maximum / multiple_of).floor() as i64).max(first);
+        self.rng.gen_range(first..=last) as f64 * multiple_of
+    }
+
+    fn string(
+        &mut self,
+        identifier: &TypeDefinitionIdentifier,
+        constraints: &TypeConstraints,
+    ) -> String {
+        let patterns = self.env.patterns(identifier);
+        let Some(generator) = patterns
+            .first()
+            .and_then(|pattern| self.generators.get(pattern))
+            .and_then(Option::as_ref)
+        else {
+            return match constraints.format {
+                Some(format) => self.formatted(format),
+                None => self.alphanumeric(constraints),
+            };
+        };
+
+        let mut candidate: String = self.rng.sample(generator);
+        for _ in 1..MAX_PATTERN_ATTEMPTS {
+            let length = candidate.chars().count() as u64;
+            let matches_all = patterns.iter().all(|pattern| {
+                self.matchers
+                    .get(pattern)
+                    .and_then(Option::as_ref)
+                    .is_none_or(|matcher| matcher.is_match(&candidate))
+            }) && constraints.min_length.is_none_or(|min| length >= min)
+                && constraints.max_length.is_none_or(|max| length <= max);
+            if matches_all {
+                break;
+            }
+            candidate = self.rng.sample(generator);
+        }
+        candidate
+    }
+
+    fn alphanumeric(&mut self, constraints: &TypeConstraints) -> String {
+        let (default_min, default_max) = STRING_LENGTH.into_inner();
+        let maximum = constraints
+            .max_length
+            .unwrap_or(default_max.max(constraints.min_length.unwrap_or(0)));
+        let minimum = constraints
+            .min_length
+            .unwrap_or(default_min.min(maximum))
+            .min(maximum);
+        let length = self.rng.gen_range(minimum..=maximum) as usize;
+        Alphanumeric.sample_string(&mut self.rng, length)
+    }
+
+    fn formatted(&mut self, format: StringFormat) -> String {
+        match format {
+            StringFormat::Email => format!(
+                "{}@example.com",
+                Alphanumeric.sample_string(&mut self.rng, 8).to_lowercase()
+            ),
+            StringFormat::Uuid => uuid::Builder::from_random_bytes(self.rng.gen())
+                .into_uuid()
+                .to_string(),
+            StringFormat::Uri => format!(
+                "https://example.com/{}",
+                Alphanumeric.sample_string(&mut self.rng, 8).to_lowercase()
+            ),
+            StringFormat::Date => format!(
+                "{:04}-{:02}-{:02}",
+                self.rng.gen_range(2000..=2030),
+                self.rng.gen_range(1..=12),
+                self.rng.gen_range(1..=28),
+            ),
+            StringFormat::Duration => format!(
+                "PT{}M{}S",
+                self.rng.gen_range(0..120),
+                self.rng.gen_range(0..60)
+            ),
+            StringFormat::DateTime => format!(
+                "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+                self.rng.gen_range(2000..=2030),
+                self.rng.gen_range(1..=12),
+                self.rng.gen_range(1..=28),
+                self.rng.gen_range(0..24),
+                self.rng.gen_range(0..60),
+                self.rng.gen_range(0..60),
+            ),
+        }
+    }
+}
+
+/// Bounds numbers are picked from: the declared ones, completed by a range of [`NUMBER_SPAN`]
+/// starting at zero when possible.
+fn number_range(constraints: &TypeConstraints) -> (f64, f64) {
+    match (constraints.minimum, constraints.maximum) {
+        (Some(minimum), Some(maximum)) => (minimum, maximum),
+        (Some(minimum), None) => (minimum, minimum.max(0.0) + NUMBER_SPAN),
+        (None, Some(maximum)) if maximum >= 0.0 => ((maximum - NUMBER_SPAN).max(0.0), maximum),
+        (None, Some(maximum)) => (maximum - NUMBER_SPAN, maximum),
+        (None, None) => (0.0, NUMBER_SPAN),
+    }
+}
+
+/// `pattern` without its leading `^` and trailing `$`, which the string generator rejects while
+/// a generated string spans the whole match anyway.
+fn unanchored(pattern: &str) -> &str {
+    let pattern = pattern.strip_prefix('^').unwrap_or(pattern);
+    match pattern.strip_suffix('$') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => pattern,
+    }
+}
+
+/// Examples and enum values are declared as strings, numeric types get them as JSON numbers when
+/// they parse.
+fn typed_example(primitive_type: Option<PrimitiveType>, example: &str) -> Value {
+    match primitive_type {
+        Some(PrimitiveType::Integer | PrimitiveType::Long) => {
+            example.parse::<i64>().map(Value::from).ok()
+        }
+        Some(PrimitiveType::Float) => example.parse::<f64>().map(Value::from).ok(),
+        Some(PrimitiveType::Boolean) => example.parse::<bool>().map(Value::from).ok(),
+        _ => None,
+    }
+    .unwrap_or_else(|| json!(example))
+}
+
+#[derive(Error, Debug)]
+pub enum GeneratorError {
+    #[error("Unknown event {0:?}")]
+    UnknownEvent(EPath),
+
+    #[error("The environment doesn't declare any events")]
+    NoEvents,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EventArgument, ObjectField, TypeDefinition};
+    use crate::util::{example_environment, std_type, type_identifier};
+    use crate::validator::{TrackedEvent, Validator};
+    use std::str::FromStr;
+
+    /// Pushes a string type with its own example to `env`, so that tests checking generated
+    /// examples don't depend on the example project.
+    fn push_sku(env: &mut Environment) -> TypeDefinitionIdentifier {
+        let sku = type_identifier("tuna-bank:client-app", "cart/Sku");
+        env.push_type_definition(TypeDefinition::new_alias(
+            sku.clone(),
+            std_type("String"),
+            Some("^SKU-[0-9]{4}$".to_string()),
+            Some("SKU-0042".to_string()),
+            TypeConstraints::default(),
+        ));
+        sku
+    }
+
+    #[test]
+    fn test_generated_events_validate() {
+        let env = example_environment();
+        let events = PayloadGenerator::of(&env, 7).generate(50, None).unwrap();
+        let validator = Validator::of(&env);
+
+        assert_eq!(events.len(), 50);
+        for event in events {
+            let tracked: TrackedEvent = serde_json::from_value(event).unwrap();
+            assert_eq!(validator.validate_tracked(&tracked), vec![]);
+        }
+    }
+
+    #[test]
+    fn test_stdlib_values_validate() {
+        let env = example_environment();
+        let mut generator = PayloadGenerator::of(&env, 3);
+        let validator = Validator::of(&env);
+
+        for name in [
+            "Boolean",
+            "Long",
+            "Date",
+            "DateTime",
+            "Duration",
+            "Uuid",
+            "Url",
+            "Email",
+            "CurrencyCode",
+            "CountryCode",
+            "LocaleTag",
+        ] {
+            let identifier = std_type(name);
+            assert!(env.type_definition(&identifier).is_some(), "{}", name);
+            let value = generator.value(&identifier);
+            let mut errors = Vec::new();
+            validator.validate_value(name, &identifier, &value, &mut errors);
+            assert_eq!(errors, vec![], "{} {}", name, value);
+        }
+    }
+
+    #[test]
+    fn test_seed_is_reproducible() {
+        let env = example_environment();
+        let on_login = EPath::from_str("common/PhoneLoginPage/OnLogin").unwrap();
+        let first = PayloadGenerator::of(&env, 42).generate(5, Some(&on_login));
+        let second = PayloadGenerator::of(&env, 42).generate(5, Some(&on_login));
+
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    #[test]
+    fn test_pattern_generated_string() {
+        let mut env = example_environment();
+        let identifier = type_identifier("tuna-bank:client-app", "common/Pin");
+        env.push_type_definition(TypeDefinition::new_alias(
+            identifier.clone(),
+            std_type("String"),
+            Some("^[0-9]{4}$".to_string()),
+            None,
+            TypeConstraints::default(),
+        ));
+        let value = PayloadGenerator::of(&env, 1).value(&identifier);
+
+        assert!(regex::Regex::new("^[0-9]{4}$")
+            .unwrap()
+            .is_match(value.as_str().unwrap()));
+    }
+
+    #[test]
+    fn test_constrained_values() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_alias(
+            std_type("EvenAge"),
+            std_type("Integer"),
+            None,
+            None,
+            TypeConstraints {
+                minimum: Some(18.0),
+                maximum: Some(30.0),
+                multiple_of: Some(2.0),
+                ..Default::default()
+            },
+        ));
+        env.push_type_definition(TypeDefinition::new_alias(
+            std_type("Email"),
+            std_type("String"),
+            None,
+            None,
+            TypeConstraints {
+                format: Some(StringFormat::Email),
+                ..Default::default()
+            },
+        ));
+        env.push_type_definition(TypeDefinition::new_alias(
+            std_type("Code"),
+            std_type("String"),
+            None,
+            None,
+            TypeConstraints {
+                min_length: Some(2),
+                max_length: Some(3),
+                ..Default::default()
+            },
+        ));
+        let validator = Validator::of(&env);
+        let mut generator = PayloadGenerator::of(&env, 3);
+
+        for _ in 0..20 {
+            for name in ["EvenAge", "Email", "Code"] {
+                let constraints = env.constraints(&std_type(name));
+                let value = generator.value(&std_type(name));
+                assert_eq!(
+                    validator.violated_constraints(&constraints, &value),
+                    Vec::<String>::new(),
+                    "{} generated {}",
+                    name,
+                    value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_object_value() {
+        let mut env = example_environment();
+        let sku = push_sku(&mut env);
+        let cart_item = type_identifier("tuna-bank:client-app", "CartItem");
+        env.push_type_definition(TypeDefinition::new_object(
+            cart_item.clone(),
+            vec![ObjectField::new("sku", sku)],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+
+        assert_eq!(
+            PayloadGenerator::of(&env, 0).value(&cart_item),
+            json!({ "sku": "SKU-0042" })
+        );
+    }
+
+    #[test]
+    fn test_recursive_object_value() {
+        fn nesting(value: &Value) -> usize {
+            let children: Vec<&Value> = match value {
+                Value::Object(fields) => fields.values().collect(),
+                Value::Array(elements) => elements.iter().collect(),
+                _ => return 0,
+            };
+            1 + children.into_iter().map(nesting).max().unwrap_or(0)
+        }
+
+        let mut env = example_environment();
+        let node = type_identifier("tuna-bank:client-app", "Node");
+        let children = TypeDefinitionIdentifier::collection(CollectionKind::List, &node);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            node.clone(),
+        ));
+        env.push_type_definition(TypeDefinition::new_object(
+            node.clone(),
+            vec![
+                ObjectField::new("name", std_type("String")),
+                ObjectField::new_optional("parent", node.clone(), None),
+                ObjectField::new("children", children),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+
+        for seed in 0..8 {
+            let value = PayloadGenerator::of(&env, seed).value(&node);
+            assert!(nesting(&value) <= MAX_DEPTH + 2);
+        }
+    }
+
+    #[test]
+    fn test_collection_value() {
+        let mut env = example_environment();
+        let sku = push_sku(&mut env);
+        let list = TypeDefinitionIdentifier::collection(CollectionKind::List, &sku);
+        let map = TypeDefinitionIdentifier::collection(CollectionKind::Map, &sku);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            sku.clone(),
+        ));
+        env.push_type_definition(TypeDefinition::new_collection(CollectionKind::Map, sku));
+        let mut generator = PayloadGenerator::of(&env, 0);
+
+        let Value::Array(elements) = generator.value(&list) else {
+            panic!("list expected");
+        };
+        assert!(COLLECTION_SIZE.contains(&elements.len()));
+        assert!(elements.iter().all(|element| element == "SKU-0042"));
+
+        let Value::Object(entries) = generator.value(&map) else {
+            panic!("map expected");
+        };
+        assert!(COLLECTION_SIZE.contains(&entries.len()));
+        assert!(entries.keys().all(|key| key.len() == MAP_KEY_LENGTH));
+    }
+
+    #[test]
+    fn test_optional_parameter_sometimes_omitted() {
+        let env = example_environment();
+        let event = Event::new(
+            "OnSearch",
+            vec![EventArgument::new_optional(
+                "referrer",
+                std_type("String"),
+                None,
+            )],
+        );
+        let mut generator = PayloadGenerator::of(&env, 0);
+        let present = (0..100)
+            .filter(|_| generator.payload(&event).get("referrer").is_some())
+            .count();
+
+        assert!(present > 0 && present < 100);
+    }
+
+    #[test]
+    fn test_unanchored() {
+        assert_eq!(unanchored("^[0-9]{4}$"), "[0-9]{4}");
+        assert_eq!(unanchored("[a-z]+\\$"), "[a-z]+\\$");
+        assert_eq!(unanchored("abc"), "abc");
+    }
+
+    #[test]
+    fn test_unknown_event() {
+        let env = example_environment();
+        let result = PayloadGenerator::of(&env, 0)
+            .generate(1, Some(&EPath::from_str("common/Nope").unwrap()));
+
+        assert!(matches!(result, Err(GeneratorError::UnknownEvent(_))));
+    }
+}
@@ -0,0 +1,3 @@
+mod generator_impl;
+
+pub use generator_impl::*;
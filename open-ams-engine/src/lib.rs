@@ -2,7 +2,11 @@ mod assets;
 pub mod resolver;
 pub mod source;
 mod util;
+pub mod codegen;
 pub mod entity;
+pub mod export;
+pub mod generator;
+pub mod validator;
 
 pub(crate) use assets::Assets;
 
@@ -0,0 +1,106 @@
+use serde_json::Value;
+
+use crate::entity::{Environment, TypeDefinition, TypeDefinitionIdentifier};
+use crate::resolver::ResolverError;
+use crate::validator::Validator;
+
+/// Checks that the default of every optional event parameter and object field is a valid value
+/// of its type.
+pub(crate) fn validate_defaults(env: &Environment) -> Result<(), ResolverError> {
+    let validator = Validator::of(env);
+
+    for (component, event) in env.events() {
+        for argument in event.arguments() {
+            let Some(default) = argument.default() else {
+                continue;
+            };
+            check(&validator, argument.type_ref(), default, || {
+                format!(
+                    "{}.{}",
+                    component.epath().extended(event.name()).to_string(),
+                    argument.name()
+                )
+            })?;
+        }
+    }
+
+    for definition in env.type_definitions() {
+        let TypeDefinition::Object(object) = definition else {
+            continue;
+        };
+        for field in object.fields() {
+            let Some(default) = field.default() else {
+                continue;
+            };
+            check(&validator, field.type_ref(), default, || {
+                format!("{}.{}", object.identifier(), field.name())
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+fn check(
+    validator: &Validator,
+    type_ref: &TypeDefinitionIdentifier,
+    default: &Value,
+    label: impl FnOnce() -> String,
+) -> Result<(), ResolverError> {
+    let mut errors = Vec::new();
+    validator.validate_value("default", type_ref, default, &mut errors);
+    match errors.first() {
+        Some(error) => Err(ResolverError::InvalidDefault {
+            parameter: label(),
+            reason: error.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{ObjectField, TypeConstraints};
+    use crate::util::{example_environment, std_type, type_identifier};
+    use serde_json::json;
+
+    fn push_cart_item(env: &mut Environment, default: Value) {
+        env.push_type_definition(TypeDefinition::new_object(
+            type_identifier("tuna-bank:client-app", "cart/CartItem"),
+            vec![ObjectField::new_optional(
+                "quantity",
+                std_type("Integer"),
+                Some(default),
+            )],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+    }
+
+    #[test]
+    fn test_valid_default() {
+        let mut env = example_environment();
+        push_cart_item(&mut env, json!(1));
+
+        assert!(validate_defaults(&env).is_ok());
+    }
+
+    #[test]
+    fn test_default_of_other_type() {
+        let mut env = example_environment();
+        push_cart_item(&mut env, json!("one"));
+
+        match validate_defaults(&env) {
+            Err(ResolverError::InvalidDefault { parameter, reason }) => {
+                assert_eq!(parameter, "tuna-bank:client-app/cart/CartItem.quantity");
+                assert_eq!(
+                    reason,
+                    "Parameter 'default' is expected to be Integer, got string"
+                );
+            }
+            other => panic!("Expected invalid default, got {:?}", other),
+        }
+    }
+}
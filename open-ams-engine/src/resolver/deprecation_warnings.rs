@@ -0,0 +1,146 @@
+use thiserror::Error;
+
+use crate::entity::{Environment, TypeDefinition, TypeDefinitionIdentifier};
+
+/// Issue worth reporting that doesn't prevent the environment from being used.
+#[derive(Error, Debug, PartialEq)]
+pub enum ResolverWarning {
+    #[error("{declaration} references deprecated type {type_definition}: {message}")]
+    DeprecatedReference {
+        declaration: String,
+        type_definition: String,
+        message: String,
+    },
+}
+
+/// Warns about event parameters and types of the target project referring to deprecated types
+/// of other projects, directly or as elements of a collection. Deprecations within the project
+/// are left to its own authors.
+pub fn deprecation_warnings(env: &Environment) -> Vec<ResolverWarning> {
+    let mut buf = Vec::new();
+
+    for (component, event) in env.events() {
+        for argument in event.arguments() {
+            check(env, argument.type_ref(), &mut buf, || {
+                format!(
+                    "{}.{}",
+                    component.epath().extended(event.name()).to_string(),
+                    argument.name()
+                )
+            });
+        }
+    }
+
+    for definition in env.type_definitions() {
+        let identifier = definition.identifier();
+        if identifier.project() != env.identifier() {
+            continue;
+        }
+        match definition {
+            TypeDefinition::Alias(alias) => {
+                check(env, alias.aliases(), &mut buf, || identifier.to_string());
+            }
+            TypeDefinition::Enum(definition) => {
+                if let Some(underlying) = definition.underlying() {
+                    check(env, underlying, &mut buf, || identifier.to_string());
+                }
+            }
+            TypeDefinition::Object(object) => {
+                for field in object.fields() {
+                    check(env, field.type_ref(), &mut buf, || {
+                        format!("{}.{}", identifier, field.name())
+                    });
+                }
+            }
+            TypeDefinition::NativeBinding(_) | TypeDefinition::Collection(_) => {}
+        }
+    }
+
+    buf
+}
+
+fn check(
+    env: &Environment,
+    type_ref: &TypeDefinitionIdentifier,
+    buf: &mut Vec<ResolverWarning>,
+    declaration: impl FnOnce() -> String,
+) {
+    let referenced = match env.type_definition(type_ref) {
+        Some(TypeDefinition::Collection(collection)) => {
+            return check(env, collection.element(), buf, declaration);
+        }
+        Some(definition) => definition,
+        None => return,
+    };
+    let Some(deprecation) = referenced.deprecation() else {
+        return;
+    };
+    if type_ref.project() == env.identifier() {
+        return;
+    }
+    buf.push(ResolverWarning::DeprecatedReference {
+        declaration: declaration(),
+        type_definition: type_ref.to_string(),
+        message: deprecation.message(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{Deprecation, ObjectField, TypeConstraints};
+    use crate::util::{example_environment, std_type, type_identifier};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_example_has_no_warnings() {
+        assert_eq!(deprecation_warnings(&example_environment()), vec![]);
+    }
+
+    #[test]
+    fn test_deprecated_type_of_another_project() {
+        let mut env = example_environment();
+        let legacy = std_type("LegacyId");
+        let local = type_identifier("tuna-bank:client-app", "cart/Sku");
+        let deprecation = Deprecation::new(None, Some("Use Uuid"), None);
+        env.push_type_definition(
+            TypeDefinition::new_native_binding(
+                legacy.clone(),
+                &HashMap::new(),
+                None,
+                None,
+                TypeConstraints::default(),
+            )
+            .with_deprecation(Some(deprecation.clone())),
+        );
+        env.push_type_definition(
+            TypeDefinition::new_native_binding(
+                local.clone(),
+                &HashMap::new(),
+                None,
+                None,
+                TypeConstraints::default(),
+            )
+            .with_deprecation(Some(deprecation)),
+        );
+        env.push_type_definition(TypeDefinition::new_object(
+            type_identifier("tuna-bank:client-app", "cart/Item"),
+            vec![
+                ObjectField::new("id", legacy),
+                ObjectField::new("sku", local),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+
+        assert_eq!(
+            deprecation_warnings(&env),
+            vec![ResolverWarning::DeprecatedReference {
+                declaration: "tuna-bank:client-app/cart/Item.id".to_string(),
+                type_definition: "ams:std/LegacyId".to_string(),
+                message: "Deprecated: Use Uuid.".to_string(),
+            }]
+        );
+    }
+}
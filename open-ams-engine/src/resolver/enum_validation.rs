@@ -0,0 +1,213 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::codegen::case_name;
+use crate::entity::target_language::TargetLanguage;
+use crate::entity::{Environment, PrimitiveType, TypeDefinition};
+use crate::resolver::ResolverError;
+
+/// Checks that every enum declares distinct values representable by its underlying type, whose
+/// generated case names never collide,
+/// native bindings must be string or number types, and that examples of enums and their aliases
+/// are one of the values.
+pub(crate) fn validate_enums(env: &Environment) -> Result<(), ResolverError> {
+    for definition in env.type_definitions() {
+        let invalid = |reason: String| ResolverError::InvalidEnum {
+            type_definition: definition.identifier().to_string(),
+            reason,
+        };
+
+        if let TypeDefinition::Enum(enum_definition) = definition {
+            if enum_definition.values().is_empty() {
+                return Err(invalid("declares no values".to_string()));
+            }
+            let Some(primitive_type) = env
+                .primitive_type(definition.identifier())
+                .filter(|primitive_type| *primitive_type != PrimitiveType::Boolean)
+            else {
+                return Err(invalid(format!(
+                    "underlying type {} is neither a string nor a number",
+                    enum_definition
+                        .underlying()
+                        .map(ToString::to_string)
+                        .unwrap_or_default()
+                )));
+            };
+
+            if let Some(underlying) = enum_definition.underlying() {
+                let mut bindings: Vec<_> = env
+                    .native_bindings(underlying)
+                    .into_iter()
+                    .flatten()
+                    .collect();
+                bindings.sort();
+                for (language, binding) in bindings {
+                    if !language.has_literals(binding) {
+                        return Err(invalid(format!(
+                            "underlying type {} is bound to {} in {}, which has no literals",
+                            underlying,
+                            binding,
+                            language.name()
+                        )));
+                    }
+                }
+            }
+
+            let mut seen = HashSet::new();
+            for value in enum_definition.values() {
+                if !seen.insert(value.value()) {
+                    return Err(invalid(format!(
+                        "value '{}' is declared more than once",
+                        value.value()
+                    )));
+                }
+                if !is_representable(primitive_type, value.value()) {
+                    return Err(invalid(format!(
+                        "value '{}' is not a valid {:?}",
+                        value.value(),
+                        primitive_type
+                    )));
+                }
+            }
+
+            for language in TargetLanguage::ALL {
+                let mut cases = HashMap::new();
+                for value in enum_definition.values() {
+                    let case = case_name(&language, value.value());
+                    if let Some(other) = cases.insert(case.clone(), value.value()) {
+                        return Err(invalid(format!(
+                            "values '{}' and '{}' both become case {} in {}",
+                            other,
+                            value.value(),
+                            case,
+                            language.name()
+                        )));
+                    }
+                }
+            }
+        }
+
+        let Some(example) = definition.example() else {
+            continue;
+        };
+        if let Some(enum_definition) = env.enum_definition(definition.identifier()) {
+            if !enum_definition.contains(example) {
+                return Err(invalid(format!(
+                    "example '{}' is not one of its values",
+                    example
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn is_representable(primitive_type: PrimitiveType, value: &str) -> bool {
+    match primitive_type {
+        PrimitiveType::String => true,
+        PrimitiveType::Integer | PrimitiveType::Long => value.parse::<i64>().is_ok(),
+        PrimitiveType::Float => value.parse::<f64>().is_ok(),
+        PrimitiveType::Boolean => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EnumValue, TypeConstraints};
+    use crate::util::{example_environment, std_type, type_identifier};
+
+    fn push_enum(env: &mut Environment, underlying: Option<&str>, values: &[&str]) {
+        env.push_type_definition(TypeDefinition::new_enum(
+            type_identifier("tuna-bank:client-app", "common/LoginMethod"),
+            underlying.map(std_type),
+            values
+                .iter()
+                .map(|value| EnumValue::new(value, None))
+                .collect(),
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+    }
+
+    fn reason(env: &Environment) -> String {
+        match validate_enums(env) {
+            Err(ResolverError::InvalidEnum { reason, .. }) => reason,
+            other => panic!("Expected invalid enum, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_valid_enum() {
+        let mut env = example_environment();
+        push_enum(&mut env, None, &["sms", "password", "biometric"]);
+
+        assert!(validate_enums(&env).is_ok());
+    }
+
+    #[test]
+    fn test_duplicate_value() {
+        let mut env = example_environment();
+        push_enum(&mut env, None, &["sms", "sms"]);
+
+        assert_eq!(reason(&env), "value 'sms' is declared more than once");
+    }
+
+    #[test]
+    fn test_colliding_case_names() {
+        let mut env = example_environment();
+        push_enum(&mut env, None, &["sms", "SMS"]);
+
+        assert_eq!(
+            reason(&env),
+            "values 'sms' and 'SMS' both become case SMS in Kotlin"
+        );
+
+        let mut env = example_environment();
+        push_enum(&mut env, None, &["date-time", "dateTime"]);
+
+        assert_eq!(
+            reason(&env),
+            "values 'date-time' and 'dateTime' both become case DATE_TIME in Kotlin"
+        );
+    }
+
+    #[test]
+    fn test_value_of_underlying_type() {
+        let mut env = example_environment();
+        push_enum(&mut env, Some("Integer"), &["1", "two"]);
+
+        assert_eq!(reason(&env), "value 'two' is not a valid Integer");
+    }
+
+    #[test]
+    fn test_underlying_type_without_literals() {
+        let mut env = example_environment();
+        push_enum(
+            &mut env,
+            Some("Uuid"),
+            &["00000000-0000-0000-0000-000000000000"],
+        );
+
+        assert_eq!(
+            reason(&env),
+            "underlying type ams:std/Uuid is bound to java.util.UUID in Kotlin, which has no literals"
+        );
+    }
+
+    #[test]
+    fn test_example_of_alias() {
+        let mut env = example_environment();
+        push_enum(&mut env, None, &["sms", "password"]);
+        env.push_type_definition(TypeDefinition::new_alias(
+            type_identifier("tuna-bank:client-app", "common/PreferredLoginMethod"),
+            type_identifier("tuna-bank:client-app", "common/LoginMethod"),
+            None,
+            Some("pin".to_string()),
+            TypeConstraints::default(),
+        ));
+
+        assert_eq!(reason(&env), "example 'pin' is not one of its values");
+    }
+}
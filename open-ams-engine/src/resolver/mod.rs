@@ -1,3 +1,13 @@
+mod default_validation;
+mod deprecation_warnings;
+mod enum_validation;
+mod object_validation;
+mod pattern_validation;
 mod resolver_impl;
 
+pub(crate) use default_validation::*;
+pub use deprecation_warnings::*;
+pub(crate) use enum_validation::*;
+pub(crate) use object_validation::*;
+pub(crate) use pattern_validation::*;
 pub use resolver_impl::*;
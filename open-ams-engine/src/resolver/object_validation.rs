@@ -0,0 +1,119 @@
+use std::collections::HashSet;
+
+use crate::entity::{Environment, TypeDefinition, TypeDefinitionIdentifier};
+use crate::resolver::ResolverError;
+
+/// Checks that no object contains itself through its required fields, directly or through other
+/// objects, as such an object can't have a finite value.
+pub(crate) fn validate_objects(env: &Environment) -> Result<(), ResolverError> {
+    for definition in env.type_definitions() {
+        let TypeDefinition::Object(_) = definition else {
+            continue;
+        };
+        let identifier = definition.identifier();
+        if let Some(path) = path_to(env, identifier, identifier, &mut HashSet::new()) {
+            return Err(ResolverError::InvalidObject {
+                type_definition: identifier.to_string(),
+                reason: format!("contains itself through field '{}'", path.join(".")),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Field path from the object at `current` to a field of the type `target`.
+fn path_to(
+    env: &Environment,
+    current: &TypeDefinitionIdentifier,
+    target: &TypeDefinitionIdentifier,
+    visited: &mut HashSet<TypeDefinitionIdentifier>,
+) -> Option<Vec<String>> {
+    let object = env.object_definition(current)?;
+    if !visited.insert(object.identifier().clone()) {
+        return None;
+    }
+
+    for field in object.fields().iter().filter(|field| field.required()) {
+        let field_object = env.object_definition(field.type_ref());
+        if field_object.is_some_and(|field_object| field_object.identifier() == target) {
+            return Some(vec![field.name().to_string()]);
+        }
+        if let Some(mut path) = path_to(env, field.type_ref(), target, visited) {
+            path.insert(0, field.name().to_string());
+            return Some(path);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{ObjectField, TypeConstraints};
+    use crate::util::{example_environment, type_identifier};
+
+    fn push_object(env: &mut Environment, path: &str, fields: &[(&str, &str)]) {
+        env.push_type_definition(TypeDefinition::new_object(
+            type_identifier("tuna-bank:client-app", path),
+            fields
+                .iter()
+                .map(|(name, type_path)| {
+                    ObjectField::new(name, type_identifier("tuna-bank:client-app", type_path))
+                })
+                .collect(),
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+    }
+
+    #[test]
+    fn test_nested_objects() {
+        let mut env = example_environment();
+        push_object(
+            &mut env,
+            "cart/CartItem",
+            &[("phone", "common/PhoneNumber")],
+        );
+        push_object(&mut env, "cart/Cart", &[("item", "cart/CartItem")]);
+
+        assert!(validate_objects(&env).is_ok());
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut env = example_environment();
+        push_object(&mut env, "tree/Node", &[("child", "tree/Child")]);
+        push_object(&mut env, "tree/Child", &[("parent", "tree/Node")]);
+
+        match validate_objects(&env) {
+            Err(ResolverError::InvalidObject {
+                type_definition,
+                reason,
+            }) => {
+                assert_eq!(type_definition, "tuna-bank:client-app/tree/Node");
+                assert_eq!(reason, "contains itself through field 'child.parent'");
+            }
+            other => panic!("Expected invalid object, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_cycle_through_optional_field() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_object(
+            type_identifier("tuna-bank:client-app", "tree/Node"),
+            vec![ObjectField::new_optional(
+                "parent",
+                type_identifier("tuna-bank:client-app", "tree/Node"),
+                None,
+            )],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+
+        assert!(validate_objects(&env).is_ok());
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::entity::Environment;
+use crate::resolver::ResolverError;
+
+/// Compiles the pattern of every resolved type and checks every example against the patterns
+/// declared along its alias chain.
+pub(crate) fn validate_patterns(env: &Environment) -> Result<(), ResolverError> {
+    let mut compiled: HashMap<&str, Regex> = HashMap::new();
+    for definition in env.type_definitions() {
+        let Some(pattern) = definition.pattern() else {
+            continue;
+        };
+        let regex = Regex::new(pattern).map_err(|error| ResolverError::InvalidPattern {
+            type_definition: definition.identifier().to_string(),
+            pattern: pattern.to_string(),
+            reason: error.to_string(),
+        })?;
+        compiled.insert(pattern, regex);
+    }
+
+    for definition in env.type_definitions() {
+        let Some(example) = definition.example() else {
+            continue;
+        };
+        for declaring in env.alias_chain(definition.identifier()) {
+            let Some(pattern) = declaring.pattern() else {
+                continue;
+            };
+            if !compiled[pattern].is_match(example) {
+                return Err(ResolverError::ExampleMismatch {
+                    type_definition: definition.identifier().to_string(),
+                    example: example.to_string(),
+                    pattern: pattern.to_string(),
+                    declared_by: declaring.identifier().to_string(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{TypeConstraints, TypeDefinition};
+    use crate::util::{example_environment, type_identifier};
+
+    #[test]
+    fn test_example_environment_is_valid() {
+        assert!(validate_patterns(&example_environment()).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_alias(
+            type_identifier("tuna-bank:client-app", "common/Broken"),
+            type_identifier("tuna-bank:client-app", "common/PhoneNumber"),
+            Some("^[0-9".to_string()),
+            None,
+            TypeConstraints::default(),
+        ));
+
+        match validate_patterns(&env) {
+            Err(ResolverError::InvalidPattern {
+                type_definition,
+                pattern,
+                ..
+            }) => {
+                assert_eq!(type_definition, "tuna-bank:client-app/common/Broken");
+                assert_eq!(pattern, "^[0-9");
+            }
+            other => panic!("Expected invalid pattern, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_example_checked_against_inherited_pattern() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_alias(
+            type_identifier("tuna-bank:client-app", "common/UsPhoneNumber"),
+            type_identifier("tuna-bank:client-app", "common/PhoneNumber"),
+            Some("^\\+1".to_string()),
+            Some("+1 541 754 3010".to_string()),
+            TypeConstraints::default(),
+        ));
+
+        match validate_patterns(&env) {
+            Err(ResolverError::ExampleMismatch {
+                type_definition,
+                declared_by,
+                ..
+            }) => {
+                assert_eq!(type_definition, "tuna-bank:client-app/common/UsPhoneNumber");
+                assert_eq!(declared_by, "tuna-bank:client-app/common/PhoneNumber");
+            }
+            other => panic!("Expected example mismatch, got {:?}", other),
+        }
+    }
+}
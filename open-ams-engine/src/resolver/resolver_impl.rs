@@ -1,7 +1,10 @@
-use crate::source::entity::{DeclarationReference, SourceEventDefinition, SourceEnvironment, SourceModuleFragment, SourceProject, SourceTypeDefinition, TypeDefinitionTypeReference};
+use std::collections::HashMap;
+
+use crate::source::entity::{ComponentDefinition, DeclarationReference, DeprecationDefinition, EPath, ParameterDefinition, SourceEventDefinition, SourceEnvironment, SourceModuleFragment, SourceProject, SourceTypeDefinition, TypeDefinitionTypeReference, TypeExpression};
 use thiserror::Error;
 
-use crate::entity::{Environment, ProjectIdentifier, TypeDefinition, TypeDefinitionIdentifier};
+use crate::entity::{CollectionKind, Component, ComponentType, Deprecation, EnumValue, Environment, Event, EventArgument, ObjectField, ProjectIdentifier, TypeDefinition, TypeDefinitionIdentifier};
+use crate::resolver::{validate_defaults, validate_enums, validate_objects, validate_patterns};
 
 #[derive(Debug)]
 pub struct Resolver<'env> {
@@ -22,12 +25,29 @@ impl<'env> Resolver<'env> {
         let target_project_name = target_project.name();
         let project_identifier = ProjectIdentifier::new(target_project_group, target_project_name);
 
-        let mut environment = Environment::empty(project_identifier);
+        let version = self
+            .env
+            .projects()
+            .iter()
+            .find(|project| project.identifier() == project_identifier)
+            .map(|project| project.manifest().version.clone())
+            .ok_or_else(|| {
+                ResolverError::EnvironmentResolveError(format!(
+                    "Target project {} is not loaded",
+                    project_identifier
+                ))
+            })?;
+
+        let mut environment = Environment::empty(project_identifier, version);
         let source_projects = self.projects_in_resolution_order()?;
 
         for project in source_projects {
             self.resolve_project_into(project, &mut environment)?
         }
+        validate_patterns(&environment)?;
+        validate_enums(&environment)?;
+        validate_objects(&environment)?;
+        validate_defaults(&environment)?;
 
         Ok(environment)
     }
@@ -45,13 +65,18 @@ impl<'env> Resolver<'env> {
         project: &SourceProject,
         context: &mut Environment,
     ) -> Result<(), ResolverError> {
-        let modules = project.modules();
-        modules.iter().for_each(|module| {
-            let types = module.definitions().types();
-            types.iter().for_each(|(type_name, type_def)| {
-                self.resolve_type_into(project, context, module, type_def, type_name);
-            });
-        });
+        let mut modules: Vec<&SourceModuleFragment> = project.modules().iter().collect();
+        modules.sort_by(|a, b| a.path().cmp(b.path()));
+
+        for module in modules {
+            for (type_name, type_def) in sorted_by_name(module.definitions().types()) {
+                self.resolve_type_into(project, context, module, type_def, type_name)?;
+            }
+
+            if project.identifier() == *context.identifier() {
+                self.resolve_module_components_into(project, context, module)?;
+            }
+        }
 
         Ok(())
     }
@@ -63,56 +88,569 @@ impl<'env> Resolver<'env> {
         module: &SourceModuleFragment,
         type_definition: &SourceTypeDefinition,
         type_name: &String,
-    ) {
+    ) -> Result<(), ResolverError> {
         let identifier = TypeDefinitionIdentifier::new(
             project.identifier(),
             module.path().extended(type_name.as_str()),
         );
+        let pattern = type_definition.pattern().cloned();
+        let example = type_definition.example().cloned();
+        let constraints = type_definition.constraints().clone();
+        let deprecation = self.resolve_deprecation(
+            type_definition.deprecated(),
+            || identifier.to_string(),
+            |reference| {
+                self.resolve_type_identifier(project, module, reference)
+                    .ok()
+                    .map(|replacement| replacement.to_string())
+            },
+        )?;
 
-        match type_definition.type_ref() {
-            TypeDefinitionTypeReference::NativeBinding => {
-                context.push_type_definition(TypeDefinition::new_native_binding(
+        let definition = match type_definition.type_ref() {
+            TypeDefinitionTypeReference::NativeBinding => TypeDefinition::new_native_binding(
+                identifier,
+                type_definition.native_bindings(),
+                pattern,
+                example,
+                constraints,
+            ),
+            TypeDefinitionTypeReference::Enum => {
+                let underlying = type_definition
+                    .underlying()
+                    .map(|underlying| self.resolve_type_identifier(project, module, underlying))
+                    .transpose()?;
+                let values = type_definition
+                    .values()
+                    .iter()
+                    .map(|value| EnumValue::new(value.value(), value.description()))
+                    .collect();
+                TypeDefinition::new_enum(
                     identifier,
-                    type_definition.native_bindings(),
-                ));
+                    underlying,
+                    values,
+                    pattern,
+                    example,
+                    constraints,
+                )
             }
-            TypeDefinitionTypeReference::Alias(declaration_ref) => {
-                context.push_type_definition(TypeDefinition::new_alias(
+            TypeDefinitionTypeReference::Object => {
+                let mut fields = Vec::new();
+                for (field_name, field) in sorted_by_name(type_definition.fields()) {
+                    check_required_without_default(field, || {
+                        format!("{}.{}", identifier, field_name)
+                    })?;
+                    let type_ref =
+                        self.resolve_type_expression(project, context, module, field.type_ref())?;
+                    let deprecation = self.resolve_deprecation(
+                        field.deprecated(),
+                        || format!("{}.{}", identifier, field_name),
+                        |reference| {
+                            self.resolve_parameter_replacement(
+                                project,
+                                identifier.path(),
+                                type_definition.fields(),
+                                reference,
+                            )
+                        },
+                    )?;
+                    fields.push(
+                        match field.required() {
+                            true => ObjectField::new(field_name, type_ref),
+                            false => ObjectField::new_optional(
+                                field_name,
+                                type_ref,
+                                field.default().cloned(),
+                            ),
+                        }
+                        .with_description(field.description())
+                        .with_deprecation(deprecation),
+                    );
+                }
+                TypeDefinition::new_object(
+                    identifier,
+                    fields,
+                    pattern,
+                    example,
+                    constraints,
+                )
+            }
+            TypeDefinitionTypeReference::Alias(expression) => {
+                let aliases = self.resolve_type_expression(project, context, module, expression)?;
+                TypeDefinition::new_alias(
                     identifier.clone(),
-                    self.resolve_type_identifier(context, project, module, declaration_ref),
-                ));
+                    aliases,
+                    pattern,
+                    example,
+                    constraints,
+                )
             }
+        };
+        context.push_type_definition(
+            definition
+                .with_description(type_definition.description())
+                .with_deprecation(deprecation),
+        );
+
+        Ok(())
+    }
+
+    fn resolve_module_components_into(
+        &self,
+        project: &SourceProject,
+        context: &mut Environment,
+        module: &SourceModuleFragment,
+    ) -> Result<(), ResolverError> {
+        let components = sorted_by_name(module.definitions().components());
+        let children: Vec<EPath> = components
+            .iter()
+            .map(|(name, _)| module.path().extended(name))
+            .collect();
+
+        let events = self.resolve_events(
+            project,
+            context,
+            module,
+            module.path(),
+            module.definitions().events(),
+        )?;
+        context.push_component(Component::new(
+            module.path().clone(),
+            ComponentType::Module,
+            events,
+            children.clone(),
+        ));
+
+        for ((_, definition), epath) in components.into_iter().zip(children) {
+            self.resolve_component_into(project, context, module, epath, definition)?;
         }
+
+        Ok(())
     }
 
-    fn resolve_type_identifier(
+    fn resolve_component_into(
         &self,
-        context: &Environment,
         project: &SourceProject,
+        context: &mut Environment,
         module: &SourceModuleFragment,
+        epath: EPath,
+        definition: &ComponentDefinition,
+    ) -> Result<(), ResolverError> {
+        let component_type = self.resolve_component_type(&epath, &definition.type_ref)?;
+        let children_definitions = sorted_by_name(&definition.children);
+        let children: Vec<EPath> = children_definitions
+            .iter()
+            .map(|(name, _)| epath.extended(name))
+            .collect();
+
+        let events = self.resolve_events(project, context, module, &epath, &definition.events)?;
+        let deprecation = self.resolve_deprecation(
+            definition.deprecated.as_ref(),
+            || epath.to_string(),
+            |reference| {
+                let (project_identifier, path) = match reference {
+                    DeclarationReference::Local { name } => {
+                        (project.identifier(), parent(&epath).extended(name))
+                    }
+                    DeclarationReference::FullyQualified {
+                        project_ref,
+                        module,
+                        name,
+                    } => (project_ref.identifier(project), module.extended(name)),
+                };
+                self.source_component(&project_identifier, &path)
+                    .map(|_| qualified_name(&project_identifier, &path))
+            },
+        )?;
+        context.push_component(
+            Component::new(epath, component_type, events, children.clone())
+                .with_description(definition.description.as_deref().unwrap_or_default())
+                .with_deprecation(deprecation),
+        );
+
+        for ((_, child), child_epath) in children_definitions.into_iter().zip(children) {
+            self.resolve_component_into(project, context, module, child_epath, child)?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_component_type(
+        &self,
+        epath: &EPath,
         declaration_ref: &DeclarationReference,
-    ) -> TypeDefinitionIdentifier {
+    ) -> Result<ComponentType, ResolverError> {
         match declaration_ref {
+            DeclarationReference::Local { name } => ComponentType::from_name(name),
+            DeclarationReference::FullyQualified { .. } => None,
+        }
+        .ok_or_else(|| ResolverError::UnknownComponentType {
+            reference: declaration_ref.to_string(),
+            component: epath.clone(),
+        })
+    }
+
+    fn resolve_events(
+        &self,
+        project: &SourceProject,
+        context: &mut Environment,
+        module: &SourceModuleFragment,
+        component: &EPath,
+        events: &HashMap<String, SourceEventDefinition>,
+    ) -> Result<Vec<Event>, ResolverError> {
+        let mut buf = Vec::new();
+        for (event_name, event) in sorted_by_name(events) {
+            let mut arguments = Vec::new();
+            for (parameter_name, parameter) in sorted_by_name(event.parameters()) {
+                check_required_without_default(parameter, || {
+                    format!("{}.{}", component.extended(event_name).to_string(), parameter_name)
+                })?;
+                let type_ref =
+                    self.resolve_type_expression(project, context, module, parameter.type_ref())?;
+                let deprecation = self.resolve_deprecation(
+                    parameter.deprecated(),
+                    || {
+                        format!(
+                            "{}.{}",
+                            component.extended(event_name).to_string(),
+                            parameter_name
+                        )
+                    },
+                    |reference| {
+                        self.resolve_parameter_replacement(
+                            project,
+                            &component.extended(event_name),
+                            event.parameters(),
+                            reference,
+                        )
+                    },
+                )?;
+                arguments.push(
+                    match parameter.required() {
+                        true => EventArgument::new(parameter_name, type_ref),
+                        false => EventArgument::new_optional(
+                            parameter_name,
+                            type_ref,
+                            parameter.default().cloned(),
+                        ),
+                    }
+                    .with_description(parameter.description().unwrap_or_default())
+                    .with_deprecation(deprecation),
+                );
+            }
+            let deprecation = self.resolve_deprecation(
+                event.deprecated(),
+                || component.extended(event_name).to_string(),
+                |reference| {
+                    let (project_identifier, component, name) = match reference {
+                        DeclarationReference::Local { name } => {
+                            (project.identifier(), component.clone(), name)
+                        }
+                        DeclarationReference::FullyQualified {
+                            project_ref,
+                            module,
+                            name,
+                        } => (project_ref.identifier(project), module.clone(), name),
+                    };
+                    self.source_events(&project_identifier, &component)
+                        .filter(|events| events.contains_key(name))
+                        .map(|_| qualified_name(&project_identifier, &component.extended(name)))
+                },
+            )?;
+            buf.push(
+                Event::new(event_name, arguments)
+                    .with_description(event.description().unwrap_or_default())
+                    .with_deprecation(deprecation),
+            );
+        }
+        Ok(buf)
+    }
+
+    /// Resolves references like [`Self::resolve_type_identifier`], pushing the definition of
+    /// every collection met into `context` the first time it is used.
+    fn resolve_type_expression(
+        &self,
+        project: &SourceProject,
+        context: &mut Environment,
+        module: &SourceModuleFragment,
+        expression: &TypeExpression,
+    ) -> Result<TypeDefinitionIdentifier, ResolverError> {
+        let (kind, element) = match expression {
+            TypeExpression::Reference(reference) => {
+                return self.resolve_type_identifier(project, module, reference)
+            }
+            TypeExpression::List(element) => (CollectionKind::List, element),
+            TypeExpression::Map(value) => (CollectionKind::Map, value),
+        };
+
+        let element = self.resolve_type_expression(project, context, module, element)?;
+        let identifier = TypeDefinitionIdentifier::collection(kind, &element);
+        if context.type_definition(&identifier).is_none() {
+            context.push_type_definition(TypeDefinition::new_collection(kind, element));
+        }
+        Ok(identifier)
+    }
+
+    fn resolve_type_identifier(
+        &self,
+        project: &SourceProject,
+        module: &SourceModuleFragment,
+        declaration_ref: &DeclarationReference,
+    ) -> Result<TypeDefinitionIdentifier, ResolverError> {
+        let resolved = match declaration_ref {
             DeclarationReference::FullyQualified {
                 project_ref,
                 module,
                 name,
             } => {
                 let project = project_ref.identifier(project);
-                TypeDefinitionIdentifier::new(
+                Some(TypeDefinitionIdentifier::new(
                     project,
                     module.extended(name.as_str()),
-                )
+                ))
+                .filter(|identifier| self.declares_type(identifier))
             }
-            DeclarationReference::Local { .. } => {
-               TypeDefinitionIdentifier::undefined()
+            DeclarationReference::Local { name } => {
+                self.local_type_candidates(project, module, name)
+                    .into_iter()
+                    .find(|identifier| self.declares_type(identifier))
             }
+        };
+
+        resolved.ok_or_else(|| ResolverError::UnresolvedReference {
+            reference: declaration_ref.to_string(),
+            project: project.identifier(),
+            module: module.path().clone(),
+        })
+    }
+
+    /// Local references are looked up in the referencing module, then in the root module of
+    /// the same project and finally in the root modules of the project dependencies.
+    fn local_type_candidates(
+        &self,
+        project: &SourceProject,
+        module: &SourceModuleFragment,
+        name: &str,
+    ) -> Vec<TypeDefinitionIdentifier> {
+        let mut candidates = vec![
+            TypeDefinitionIdentifier::new(project.identifier(), module.path().extended(name)),
+            TypeDefinitionIdentifier::new(project.identifier(), EPath::empty().extended(name)),
+        ];
+        for dependency in &project.manifest().dependencies {
+            candidates.push(TypeDefinitionIdentifier::new(
+                dependency.identifier(),
+                EPath::empty().extended(name),
+            ));
         }
+        candidates
     }
+
+    /// Resolves `deprecated`, failing when its replacement can't be found by `resolve_replacement`.
+    fn resolve_deprecation(
+        &self,
+        deprecated: Option<&DeprecationDefinition>,
+        declaration: impl FnOnce() -> String,
+        resolve_replacement: impl FnOnce(&DeclarationReference) -> Option<String>,
+    ) -> Result<Option<Deprecation>, ResolverError> {
+        let Some(deprecated) = deprecated else {
+            return Ok(None);
+        };
+        let replaced_by = match deprecated.replaced_by() {
+            Some(reference) => Some(resolve_replacement(reference).ok_or_else(|| {
+                ResolverError::UnresolvedReplacement {
+                    reference: reference.to_string(),
+                    declaration: declaration(),
+                }
+            })?),
+            None => None,
+        };
+        Ok(Some(Deprecation::new(
+            deprecated.since().cloned(),
+            deprecated.reason(),
+            replaced_by,
+        )))
+    }
+
+    /// Local replacements of parameters and fields are their siblings, fully qualified ones
+    /// point to a parameter of an event or to a field of an object type.
+    fn resolve_parameter_replacement(
+        &self,
+        project: &SourceProject,
+        owner: &EPath,
+        siblings: &HashMap<String, ParameterDefinition>,
+        reference: &DeclarationReference,
+    ) -> Option<String> {
+        let (project_identifier, owner, name) = match reference {
+            DeclarationReference::Local { name } => {
+                return siblings
+                    .contains_key(name)
+                    .then(|| format!("{}.{}", qualified_name(&project.identifier(), owner), name));
+            }
+            DeclarationReference::FullyQualified {
+                project_ref,
+                module,
+                name,
+            } => (project_ref.identifier(project), module, name),
+        };
+        self.source_parameters(&project_identifier, owner)
+            .filter(|parameters| parameters.contains_key(name))
+            .map(|_| format!("{}.{}", qualified_name(&project_identifier, owner), name))
+    }
+
+    fn source_modules<'a>(
+        &'a self,
+        project: &'a ProjectIdentifier,
+    ) -> impl Iterator<Item = &'env SourceModuleFragment> + 'a {
+        self.env
+            .projects()
+            .iter()
+            .filter(move |project_candidate| project_candidate.identifier() == *project)
+            .flat_map(|project| project.modules())
+    }
+
+    /// Component declared at `epath`, looked up through the children of the module components.
+    fn source_component(
+        &self,
+        project: &ProjectIdentifier,
+        epath: &EPath,
+    ) -> Option<&'env ComponentDefinition> {
+        self.source_modules(project).find_map(|module| {
+            let segments = epath
+                .segments
+                .strip_prefix(module.path().segments.as_slice())?;
+            let (first, rest) = segments.split_first()?;
+            let root = module.definitions().components().get(first)?;
+            rest.iter()
+                .try_fold(root, |component, name| component.children.get(name))
+        })
+    }
+
+    /// Events of the module or of the component at `component`.
+    fn source_events(
+        &self,
+        project: &ProjectIdentifier,
+        component: &EPath,
+    ) -> Option<&'env HashMap<String, SourceEventDefinition>> {
+        self.source_modules(project)
+            .find(|module| module.path() == component)
+            .map(|module| module.definitions().events())
+            .or_else(|| {
+                self.source_component(project, component)
+                    .map(|component| &component.events)
+            })
+    }
+
+    /// Parameters of the event or fields of the object type declared at `owner`.
+    fn source_parameters(
+        &self,
+        project: &ProjectIdentifier,
+        owner: &EPath,
+    ) -> Option<&'env HashMap<String, ParameterDefinition>> {
+        let (name, _) = owner.segments.split_last()?;
+        let parent = parent(owner);
+        self.source_events(project, &parent)
+            .and_then(|events| events.get(name))
+            .map(|event| event.parameters())
+            .or_else(|| {
+                self.source_modules(project)
+                    .filter(|module| *module.path() == parent)
+                    .find_map(|module| module.definitions().types().get(name))
+                    .map(|definition| definition.fields())
+            })
+    }
+
+    fn declares_type(&self, identifier: &TypeDefinitionIdentifier) -> bool {
+        let Some((name, module_segments)) = identifier.path().segments.split_last() else {
+            return false;
+        };
+
+        self.env
+            .projects()
+            .iter()
+            .filter(|project| project.identifier() == *identifier.project())
+            .flat_map(|project| project.modules())
+            .filter(|module| module.path().segments == module_segments)
+            .any(|module| module.definitions().types().contains_key(name))
+    }
+}
+
+/// A default only makes sense for parameters that may be left out.
+fn check_required_without_default(
+    parameter: &ParameterDefinition,
+    label: impl FnOnce() -> String,
+) -> Result<(), ResolverError> {
+    if parameter.required() && parameter.default().is_some() {
+        return Err(ResolverError::InvalidDefault {
+            parameter: label(),
+            reason: "a required parameter can't declare a default".to_string(),
+        });
+    }
+    Ok(())
+}
+
+fn parent(epath: &EPath) -> EPath {
+    let segments = epath.segments.split_last().map(|(_, parent)| parent);
+    EPath::new(segments.unwrap_or_default().to_vec())
+}
+
+/// Name of a declaration in diagnostics, e.g. `tuna-bank:client-app/common/PhoneLoginPage`.
+fn qualified_name(project: &ProjectIdentifier, path: &EPath) -> String {
+    TypeDefinitionIdentifier::new(project.clone(), path.clone()).to_string()
+}
+
+fn sorted_by_name<T>(definitions: &HashMap<String, T>) -> Vec<(&String, &T)> {
+    let mut buf: Vec<(&String, &T)> = definitions.iter().collect();
+    buf.sort_by(|a, b| a.0.cmp(b.0));
+    buf
 }
 
 #[derive(Error, Debug)]
 pub enum ResolverError {
     #[error("Failed to resolve environment: {0}")]
     EnvironmentResolveError(String),
+
+    #[error("Unresolved reference '{reference}' in {project}/{module:?}")]
+    UnresolvedReference {
+        reference: String,
+        project: ProjectIdentifier,
+        module: EPath,
+    },
+
+    #[error("Unknown component type '{reference}' of component {component:?}")]
+    UnknownComponentType { reference: String, component: EPath },
+
+    #[error("Invalid pattern '{pattern}' of type {type_definition}: {reason}")]
+    InvalidPattern {
+        type_definition: String,
+        pattern: String,
+        reason: String,
+    },
+
+    #[error("Invalid enum {type_definition}: {reason}")]
+    InvalidEnum {
+        type_definition: String,
+        reason: String,
+    },
+
+    #[error("Unresolved replacement '{reference}' of deprecated {declaration}")]
+    UnresolvedReplacement {
+        reference: String,
+        declaration: String,
+    },
+
+    #[error("Invalid default of parameter {parameter}: {reason}")]
+    InvalidDefault { parameter: String, reason: String },
+
+    #[error("Invalid object {type_definition}: {reason}")]
+    InvalidObject {
+        type_definition: String,
+        reason: String,
+    },
+
+    #[error("Example '{example}' of type {type_definition} does not match pattern '{pattern}' declared by {declared_by}")]
+    ExampleMismatch {
+        type_definition: String,
+        example: String,
+        pattern: String,
+        declared_by: String,
+    },
 }
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, fmt};
 
-use super::{DeclarationReference, SourceEventDefinition};
+use super::{DeclarationReference, DeprecationDefinition, SourceEventDefinition};
 use crate::util::custom_deserialize_map_or_seq;
 use serde::{Deserialize, Serialize};
 
@@ -15,4 +15,9 @@ pub struct ComponentDefinition {
     pub events: HashMap<String, SourceEventDefinition>,
     #[serde(default = "HashMap::new")]
     pub children: HashMap<String, ComponentDefinition>,
+    /// Markdown description of the component.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<DeprecationDefinition>,
 }
@@ -1,4 +1,5 @@
 use super::{Version, VersionParsingError};
+use crate::entity::ProjectIdentifier;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use thiserror::Error;
@@ -26,6 +27,12 @@ impl DependencyReference {
             DependencyReference::Managed { group, .. } => group == "ams",
         }
     }
+
+    pub fn identifier(&self) -> ProjectIdentifier {
+        match self {
+            DependencyReference::Managed { group, name, .. } => ProjectIdentifier::new(group, name),
+        }
+    }
 }
 
 impl Serialize for DependencyReference {
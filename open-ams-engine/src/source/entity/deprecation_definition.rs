@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+
+use super::{DeclarationReference, Version};
+
+/// Marks a type, event, parameter or component as deprecated. The replacement is looked up
+/// like any other reference, local names pointing to siblings of the deprecated declaration.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeprecationDefinition {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    since: Option<Version>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replaced_by: Option<DeclarationReference>,
+}
+
+impl DeprecationDefinition {
+    /// Version of the project the declaration got deprecated in.
+    pub fn since(&self) -> Option<&Version> {
+        self.since.as_ref()
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub fn replaced_by(&self) -> Option<&DeclarationReference> {
+        self.replaced_by.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_deserialize() {
+        let yaml = "since: 1.2.0
+reason: Passcodes are sent by email now
+replacedBy: OnEmailLogin
+";
+        let deprecation: DeprecationDefinition = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            deprecation.since(),
+            Some(&Version::from_str("1.2.0").unwrap())
+        );
+        assert_eq!(
+            deprecation.reason(),
+            Some("Passcodes are sent by email now")
+        );
+        assert_eq!(
+            deprecation.replaced_by(),
+            Some(&DeclarationReference::local("OnEmailLogin".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_deserialize_empty() {
+        let deprecation: DeprecationDefinition = serde_yaml::from_str("{}").unwrap();
+
+        assert_eq!(deprecation.since(), None);
+        assert_eq!(deprecation.replaced_by(), None);
+    }
+}
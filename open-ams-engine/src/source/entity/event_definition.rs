@@ -2,10 +2,29 @@ use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
 
-use super::ParameterDefinition;
+use super::{DeprecationDefinition, ParameterDefinition};
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Default)]
 pub struct SourceEventDefinition {
     #[serde(default = "HashMap::new")]
     parameters: HashMap<String, ParameterDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deprecated: Option<DeprecationDefinition>,
+}
+
+impl SourceEventDefinition {
+    pub fn parameters(&self) -> &HashMap<String, ParameterDefinition> {
+        &self.parameters
+    }
+
+    /// Markdown description of the event.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn deprecated(&self) -> Option<&DeprecationDefinition> {
+        self.deprecated.as_ref()
+    }
 }
@@ -2,6 +2,7 @@ mod component_definition;
 mod declaration_reference;
 mod declaration_reference_filter;
 mod dependency;
+mod deprecation_definition;
 mod environment;
 mod event_definition;
 mod manifest;
@@ -11,12 +12,14 @@ mod parameter_definition;
 mod project;
 mod project_reference;
 mod type_definition;
+mod type_expression;
 mod version;
 
 pub use component_definition::*;
 pub use declaration_reference::*;
 pub use declaration_reference_filter::*;
 pub use dependency::*;
+pub use deprecation_definition::*;
 pub use environment::*;
 pub use crate::entity::epath::*;
 pub use event_definition::*;
@@ -28,4 +31,5 @@ pub use project::*;
 pub use project_reference::*;
 pub use crate::entity::target_language::*;
 pub use type_definition::*;
+pub use type_expression::*;
 pub use version::*;
@@ -1,9 +1,65 @@
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use super::DeclarationReference;
+use super::{DeprecationDefinition, TypeExpression};
 
+/// Parameter of an event or field of an object. It is required unless it declares a `default`
+/// or `required: false`.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ParameterDefinition {
     #[serde(rename = "type")]
-    type_ref: DeclarationReference,
+    type_ref: TypeExpression,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    required: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    default: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deprecated: Option<DeprecationDefinition>,
+}
+
+impl ParameterDefinition {
+    pub fn type_ref(&self) -> &TypeExpression {
+        &self.type_ref
+    }
+
+    /// Whether the parameter has to be present, as declared or implied by a missing `default`.
+    pub fn required(&self) -> bool {
+        self.required.unwrap_or(self.default.is_none())
+    }
+
+    pub fn default(&self) -> Option<&Value> {
+        self.default.as_ref()
+    }
+
+    /// Markdown description of the parameter.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn deprecated(&self) -> Option<&DeprecationDefinition> {
+        self.deprecated.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_optional() {
+        let required: ParameterDefinition = serde_yaml::from_str("type: String").unwrap();
+        let optional: ParameterDefinition =
+            serde_yaml::from_str("type: String\nrequired: false").unwrap();
+        let defaulted: ParameterDefinition =
+            serde_yaml::from_str("type: Integer\ndefault: 3").unwrap();
+
+        assert!(required.required());
+        assert!(!optional.required());
+        assert_eq!(optional.default(), None);
+        assert!(!defaulted.required());
+        assert_eq!(defaulted.default(), Some(&json!(3)));
+    }
 }
@@ -28,6 +28,10 @@ impl SourceProject {
     pub fn identifier(&self) -> ProjectIdentifier {
         self.manifest.identifier()
     }
+
+    pub fn manifest(&self) -> &SourceManifest {
+        &self.manifest
+    }
     
     pub fn modules(&self) -> &[SourceModuleFragment] {
         self.module_fragments.as_slice()
@@ -3,7 +3,11 @@ use std::str::FromStr;
 
 use serde::{Deserialize, Serialize};
 
-use super::{DeclarationReference, TargetLanguage};
+use super::{
+    DeclarationReference, DeprecationDefinition, ParameterDefinition, TargetLanguage,
+    TypeExpression,
+};
+use crate::entity::TypeConstraints;
 use serde::de::{self, Visitor};
 use serde::{Deserializer, Serializer};
 
@@ -13,24 +17,97 @@ pub struct SourceTypeDefinition {
     type_ref: TypeDefinitionTypeReference,
     pattern: Option<String>,
     example: Option<String>,
+    #[serde(flatten)]
+    constraints: TypeConstraints,
     #[serde(rename = "nativeBindings", default = "HashMap::new")]
     native_bindings: HashMap<TargetLanguage, String>,
+    underlying: Option<DeclarationReference>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    values: Vec<SourceEnumValue>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    fields: HashMap<String, ParameterDefinition>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    deprecated: Option<DeprecationDefinition>,
 }
 
 #[derive(Debug, PartialEq)]
 pub enum TypeDefinitionTypeReference {
     NativeBinding,
-    Alias(DeclarationReference),
+    Enum,
+    Object,
+    Alias(TypeExpression),
+}
+
+/// Value of an `Enum` type, either just the value or the value with a description.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SourceEnumValue {
+    Plain(String),
+    Described {
+        value: String,
+        description: Option<String>,
+    },
+}
+
+impl SourceEnumValue {
+    pub fn value(&self) -> &str {
+        match self {
+            SourceEnumValue::Plain(value) => value,
+            SourceEnumValue::Described { value, .. } => value,
+        }
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            SourceEnumValue::Plain(_) => None,
+            SourceEnumValue::Described { description, .. } => description.as_deref(),
+        }
+    }
 }
 
 impl SourceTypeDefinition {
     pub fn native_bindings(&self) -> &HashMap<TargetLanguage, String> {
         &self.native_bindings
     }
-    
+
     pub fn type_ref(&self) -> &TypeDefinitionTypeReference {
         &self.type_ref
     }
+
+    pub fn pattern(&self) -> Option<&String> {
+        self.pattern.as_ref()
+    }
+
+    pub fn example(&self) -> Option<&String> {
+        self.example.as_ref()
+    }
+
+    pub fn constraints(&self) -> &TypeConstraints {
+        &self.constraints
+    }
+
+    pub fn underlying(&self) -> Option<&DeclarationReference> {
+        self.underlying.as_ref()
+    }
+
+    pub fn values(&self) -> &[SourceEnumValue] {
+        &self.values
+    }
+
+    pub fn fields(&self) -> &HashMap<String, ParameterDefinition> {
+        &self.fields
+    }
+
+    /// Markdown description of the type.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn deprecated(&self) -> Option<&DeprecationDefinition> {
+        self.deprecated.as_ref()
+    }
 }
 
 impl Serialize for TypeDefinitionTypeReference {
@@ -40,6 +117,8 @@ impl Serialize for TypeDefinitionTypeReference {
     {
         match self {
             TypeDefinitionTypeReference::NativeBinding => serializer.serialize_str("NativeBinding"),
+            TypeDefinitionTypeReference::Enum => serializer.serialize_str("Enum"),
+            TypeDefinitionTypeReference::Object => serializer.serialize_str("Object"),
             TypeDefinitionTypeReference::Alias(decl) => decl.serialize(serializer),
         }
     }
@@ -65,8 +144,10 @@ impl<'de> Deserialize<'de> for TypeDefinitionTypeReference {
             {
                 match value {
                     "NativeBinding" => Ok(TypeDefinitionTypeReference::NativeBinding),
+                    "Enum" => Ok(TypeDefinitionTypeReference::Enum),
+                    "Object" => Ok(TypeDefinitionTypeReference::Object),
                     _ => Ok(TypeDefinitionTypeReference::Alias(
-                        DeclarationReference::from_str(value).map_err(|e| de::Error::custom(e))?,
+                        TypeExpression::from_str(value).map_err(|e| de::Error::custom(e))?,
                     )),
                 }
             }
@@ -75,3 +156,79 @@ impl<'de> Deserialize<'de> for TypeDefinitionTypeReference {
         deserializer.deserialize_any(TypeDefVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deserialize_enum() {
+        let yaml = "type: Enum
+values:
+  - sms
+  - value: password
+    description: Password chosen at sign up
+";
+        let definition: SourceTypeDefinition = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(definition.type_ref(), &TypeDefinitionTypeReference::Enum);
+        assert_eq!(definition.underlying(), None);
+        assert_eq!(
+            definition
+                .values()
+                .iter()
+                .map(|value| (value.value(), value.description()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("sms", None),
+                ("password", Some("Password chosen at sign up"))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deserialize_object() {
+        let yaml = "type: Object
+fields:
+  sku:
+    type: String
+  quantity:
+    type: ams:std/Integer
+";
+        let definition: SourceTypeDefinition = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(definition.type_ref(), &TypeDefinitionTypeReference::Object);
+        assert_eq!(
+            definition.fields()["quantity"].type_ref(),
+            &TypeExpression::from_str("ams:std/Integer").unwrap()
+        );
+        assert_eq!(
+            definition.fields()["sku"].type_ref(),
+            &TypeExpression::from_str("String").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_deserialize_description() {
+        let yaml = "type: Object
+description: |
+  Item of a cart.
+
+  Priced per unit.
+fields:
+  sku:
+    type: String
+    description: Stock keeping unit
+";
+        let definition: SourceTypeDefinition = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(
+            definition.description(),
+            Some("Item of a cart.\n\nPriced per unit.\n")
+        );
+        assert_eq!(
+            definition.fields()["sku"].description(),
+            Some("Stock keeping unit")
+        );
+    }
+}
@@ -0,0 +1,172 @@
+use core::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use thiserror::Error;
+
+use super::{DeclarationReference, DeclarationReferenceError};
+
+/// Type of a parameter, field or alias: a reference to a declared type or a collection of them,
+/// written as `List<T>` or `Map<String, T>`.
+#[derive(Debug, PartialEq)]
+pub enum TypeExpression {
+    Reference(DeclarationReference),
+    List(Box<TypeExpression>),
+    Map(Box<TypeExpression>),
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum TypeExpressionError {
+    #[error("Invalid type reference: {0}")]
+    InvalidReference(#[from] DeclarationReferenceError),
+    #[error("Unbalanced angle brackets in '{0}'")]
+    Unbalanced(String),
+    #[error("Map keys have to be String, got '{0}'")]
+    UnsupportedKey(String),
+    #[error("Unknown collection '{0}', expected List or Map")]
+    UnknownCollection(String),
+}
+
+impl FromStr for TypeExpression {
+    type Err = TypeExpressionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let Some((constructor, arguments)) = s.split_once('<') else {
+            return Ok(TypeExpression::Reference(s.parse()?));
+        };
+        let arguments = arguments
+            .strip_suffix('>')
+            .ok_or_else(|| TypeExpressionError::Unbalanced(s.to_string()))?;
+
+        match constructor.trim() {
+            "List" => Ok(TypeExpression::List(Box::new(arguments.parse()?))),
+            "Map" => {
+                let (key, value) = split_top_level_comma(arguments)
+                    .ok_or_else(|| TypeExpressionError::Unbalanced(s.to_string()))?;
+                match key.trim() {
+                    "String" | "ams:std/String" => {}
+                    key => return Err(TypeExpressionError::UnsupportedKey(key.to_string())),
+                }
+                Ok(TypeExpression::Map(Box::new(value.parse()?)))
+            }
+            constructor => Err(TypeExpressionError::UnknownCollection(
+                constructor.to_string(),
+            )),
+        }
+    }
+}
+
+/// Splits `String, List<T>` at the first comma outside of angle brackets.
+fn split_top_level_comma(s: &str) -> Option<(&str, &str)> {
+    let mut depth = 0;
+    for (index, c) in s.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => return Some((&s[..index], &s[index + 1..])),
+            _ => {}
+        }
+    }
+    None
+}
+
+impl fmt::Display for TypeExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeExpression::Reference(reference) => write!(f, "{}", reference.to_string()),
+            TypeExpression::List(element) => write!(f, "List<{}>", element),
+            TypeExpression::Map(value) => write!(f, "Map<String, {}>", value),
+        }
+    }
+}
+
+impl Serialize for TypeExpression {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+struct TypeExpressionVisitor;
+
+impl<'de> Visitor<'de> for TypeExpressionVisitor {
+    type Value = TypeExpression;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a type reference such as `PhoneNumber` or `List<String>`")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        value.parse::<TypeExpression>().map_err(de::Error::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for TypeExpression {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(TypeExpressionVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reference(s: &str) -> Box<TypeExpression> {
+        Box::new(TypeExpression::Reference(s.parse().unwrap()))
+    }
+
+    #[test]
+    fn test_from_str_reference() {
+        assert_eq!(
+            "PhoneNumber".parse::<TypeExpression>(),
+            Ok(*reference("PhoneNumber"))
+        );
+    }
+
+    #[test]
+    fn test_from_str_collections() {
+        assert_eq!(
+            "List<ams:std/String>".parse::<TypeExpression>(),
+            Ok(TypeExpression::List(reference("ams:std/String")))
+        );
+        assert_eq!(
+            "Map<String, List<Integer>>".parse::<TypeExpression>(),
+            Ok(TypeExpression::Map(Box::new(TypeExpression::List(
+                reference("Integer")
+            ))))
+        );
+    }
+
+    #[test]
+    fn test_from_str_invalid() {
+        assert_eq!(
+            "Map<Integer, String>".parse::<TypeExpression>(),
+            Err(TypeExpressionError::UnsupportedKey("Integer".to_string()))
+        );
+        assert_eq!(
+            "List<String".parse::<TypeExpression>(),
+            Err(TypeExpressionError::Unbalanced("List<String".to_string()))
+        );
+        assert_eq!(
+            "Set<String>".parse::<TypeExpression>(),
+            Err(TypeExpressionError::UnknownCollection("Set".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let expression: TypeExpression = "Map< String ,List<Integer> >".parse().unwrap();
+
+        assert_eq!(expression.to_string(), "Map<String, List<Integer>>");
+    }
+}
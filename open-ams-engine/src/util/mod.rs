@@ -1,2 +1,6 @@
 mod custom_serialization;
 pub(crate) use custom_serialization::*;
+#[cfg(test)]
+mod test_fixtures;
+#[cfg(test)]
+pub(crate) use test_fixtures::*;
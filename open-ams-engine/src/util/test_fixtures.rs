@@ -0,0 +1,28 @@
+use std::str::FromStr;
+
+use crate::entity::{Environment, ProjectIdentifier, TypeDefinitionIdentifier};
+use crate::resolver::Resolver;
+use crate::source::entity::{EPath, SourceEnvironment};
+
+pub(crate) fn example_environment() -> Environment {
+    let mut environment: SourceEnvironment = SourceEnvironment::default().unwrap();
+    environment.load_local("./../example").unwrap();
+    environment.set_target_project(environment.projects()[1].identifier());
+
+    Resolver::of(&environment).resolve().unwrap()
+}
+
+/// Identifier of the type at `path` in `project`, e.g. `common/PhoneNumber` in
+/// `tuna-bank:client-app`.
+pub(crate) fn type_identifier(project: &str, path: &str) -> TypeDefinitionIdentifier {
+    let (group, name) = project.split_once(':').unwrap();
+    TypeDefinitionIdentifier::new(
+        ProjectIdentifier::new(group, name),
+        EPath::from_str(path).unwrap(),
+    )
+}
+
+/// Identifier of the standard library type `name`, e.g. `String`.
+pub(crate) fn std_type(name: &str) -> TypeDefinitionIdentifier {
+    type_identifier("ams:std", name)
+}
@@ -0,0 +1,7 @@
+mod summary;
+mod tracked_event;
+mod validator_impl;
+
+pub use summary::*;
+pub use tracked_event::*;
+pub use validator_impl::*;
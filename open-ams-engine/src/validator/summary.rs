@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use crate::entity::Environment;
+use crate::source::entity::EPath;
+use crate::validator::ValidationError;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct EventStats {
+    pub passed: usize,
+    pub failed: usize,
+    /// Number of occurrences of every distinct [`ValidationError::summary`].
+    pub errors: BTreeMap<String, usize>,
+}
+
+/// Running tally of validated events, telling which events of the spec were seen and which
+/// never fired.
+#[derive(Debug, Default)]
+pub struct ValidationSummary {
+    stats: BTreeMap<EPath, EventStats>,
+    malformed: usize,
+}
+
+/// Machine-readable outcome of validating a batch of events against an [`Environment`].
+#[derive(Debug, PartialEq, Serialize)]
+pub struct AuditReport {
+    pub total: usize,
+    pub passed: usize,
+    pub failed: usize,
+    pub malformed: usize,
+    /// Stats of seen events declared in the spec.
+    pub events: BTreeMap<EPath, EventStats>,
+    /// Occurrences of seen events the spec doesn't declare.
+    pub unknown_events: BTreeMap<EPath, usize>,
+    /// Spec events that never occurred, in spec order.
+    pub dead_events: Vec<EPath>,
+}
+
+impl ValidationSummary {
+    pub fn record(&mut self, event: &EPath, errors: &[ValidationError]) {
+        let stats = self.stats.entry(event.clone()).or_default();
+        if errors.is_empty() {
+            stats.passed += 1;
+        } else {
+            stats.failed += 1;
+        }
+        for error in errors {
+            *stats.errors.entry(error.summary()).or_default() += 1;
+        }
+    }
+
+    /// Records an event that couldn't be parsed at all.
+    pub fn record_malformed(&mut self) {
+        self.malformed += 1;
+    }
+
+    /// Stats of every seen event, including events unknown to the spec.
+    pub fn seen(&self) -> &BTreeMap<EPath, EventStats> {
+        &self.stats
+    }
+
+    pub fn malformed(&self) -> usize {
+        self.malformed
+    }
+
+    /// Events of `env` that were never recorded, in spec order.
+    pub fn never_fired(&self, env: &Environment) -> Vec<EPath> {
+        env.events()
+            .map(|(component, event)| component.epath().extended(event.name()))
+            .filter(|path| !self.stats.contains_key(path))
+            .collect()
+    }
+
+    pub fn report(&self, env: &Environment) -> AuditReport {
+        let mut events = BTreeMap::new();
+        let mut unknown_events = BTreeMap::new();
+        for (path, stats) in &self.stats {
+            if env.event(path).is_some() {
+                events.insert(path.clone(), stats.clone());
+            } else {
+                unknown_events.insert(path.clone(), stats.passed + stats.failed);
+            }
+        }
+        let passed: usize = self.stats.values().map(|stats| stats.passed).sum();
+        let failed: usize = self.stats.values().map(|stats| stats.failed).sum();
+
+        AuditReport {
+            total: passed + failed + self.malformed,
+            passed,
+            failed,
+            malformed: self.malformed,
+            events,
+            unknown_events,
+            dead_events: self.never_fired(env),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::example_environment;
+    use crate::validator::Validator;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_summary() {
+        let env = example_environment();
+        let on_login = EPath::from_str("common/PhoneLoginPage/OnLogin").unwrap();
+        let mut summary = ValidationSummary::default();
+        summary.record(&on_login, &[]);
+        summary.record(
+            &on_login,
+            &[ValidationError::MissingParameter {
+                parameter: "phone".to_string(),
+            }],
+        );
+
+        assert_eq!(
+            summary.seen()[&on_login],
+            EventStats {
+                passed: 1,
+                failed: 1,
+                errors: BTreeMap::from([("Missing parameter 'phone'".to_string(), 1)]),
+            }
+        );
+        assert_eq!(
+            summary.never_fired(&env),
+            vec![
+                EPath::from_str("common/UserLoggedIn").unwrap(),
+                EPath::from_str("common/PhoneLoginPage/LogingButton/OnClick").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let env = example_environment();
+        let on_login = EPath::from_str("common/PhoneLoginPage/OnLogin").unwrap();
+        let unknown = EPath::from_str("common/PhoneLoginPage/OnLogout").unwrap();
+        let validator = Validator::of(&env);
+        let mut summary = ValidationSummary::default();
+        for (event, payload) in [
+            (
+                &on_login,
+                json!({ "phone": "+1-5417543010", "passcode": "1" }),
+            ),
+            (&on_login, json!({ "phone": "+1-5417543010" })),
+            (&unknown, json!({})),
+        ] {
+            summary.record(event, &validator.validate(event, &payload));
+        }
+        summary.record_malformed();
+        let report = summary.report(&env);
+
+        assert_eq!((report.total, report.passed, report.failed), (4, 1, 2));
+        assert_eq!(report.malformed, 1);
+        assert_eq!(report.events.len(), 1);
+        assert_eq!(report.unknown_events, BTreeMap::from([(unknown, 1)]));
+        assert_eq!(report.dead_events.len(), 2);
+        assert_eq!(
+            serde_json::to_value(&report).unwrap()["events"]["common/PhoneLoginPage/OnLogin"],
+            json!({
+                "passed": 1,
+                "failed": 1,
+                "errors": { "Missing parameter 'passcode'": 1 },
+            })
+        );
+    }
+}
@@ -0,0 +1,62 @@
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::source::entity::EPath;
+
+/// Envelope of a single analytics event as sent by an instrumented client, e.g.
+/// `{"event": "common/PhoneLoginPage/OnLogin", "properties": {"phone": "+1-5417543010"}}`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct TrackedEvent {
+    pub event: EPath,
+    #[serde(default = "empty_properties")]
+    pub properties: Value,
+}
+
+fn empty_properties() -> Value {
+    Value::Object(Default::default())
+}
+
+impl TrackedEvent {
+    /// Parses a request body holding a single event, a JSON array of events or newline
+    /// delimited events. Every event is parsed on its own, so one malformed line doesn't reject
+    /// the rest of the batch.
+    pub fn parse_batch(body: &str) -> Vec<Result<TrackedEvent, serde_json::Error>> {
+        match serde_json::from_str::<Value>(body) {
+            Ok(Value::Array(events)) => events.into_iter().map(serde_json::from_value).collect(),
+            Ok(event) => vec![serde_json::from_value(event)],
+            Err(_) => body
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(serde_json::from_str)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_parse_batch() {
+        let single = TrackedEvent::parse_batch(r#"{"event": "common/UserLoggedIn"}"#);
+        let array = TrackedEvent::parse_batch(r#"[{"event": "a/B"}, {"event": "a/C"}]"#);
+        let ndjson = TrackedEvent::parse_batch(
+            "{\"event\": \"a/B\", \"properties\": {\"x\": 1}}\n\nnot json\n",
+        );
+
+        assert_eq!(
+            single[0].as_ref().unwrap(),
+            &TrackedEvent {
+                event: EPath::from_str("common/UserLoggedIn").unwrap(),
+                properties: json!({}),
+            }
+        );
+        assert_eq!(array.len(), 2);
+        assert_eq!(ndjson.len(), 2);
+        assert_eq!(ndjson[0].as_ref().unwrap().properties, json!({ "x": 1 }));
+        assert!(ndjson[1].is_err());
+    }
+}
@@ -0,0 +1,777 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+use serde_json::{Map, Value};
+use thiserror::Error;
+
+use crate::entity::{
+    CollectionKind, CollectionTypeDefinition, Environment, Event, ObjectTypeDefinition,
+    PrimitiveType, StringFormat, TypeConstraints, TypeDefinitionIdentifier,
+};
+use crate::source::entity::EPath;
+use crate::validator::TrackedEvent;
+
+/// Validates event payloads against a resolved [`Environment`].
+///
+/// Patterns of all type definitions are compiled once up front, so a single validator can be
+/// shared for all payloads of an ingestion pipeline.
+#[derive(Debug)]
+pub struct Validator<'env> {
+    env: &'env Environment,
+    patterns: HashMap<&'env str, Option<Regex>>,
+    formats: HashMap<StringFormat, Regex>,
+}
+
+impl<'env> Validator<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        let patterns = env
+            .type_definitions()
+            .iter()
+            .filter_map(|definition| definition.pattern())
+            .map(|pattern| (pattern, Regex::new(pattern).ok()))
+            .collect();
+
+        let formats = StringFormat::ALL
+            .iter()
+            .map(|format| {
+                let regex = Regex::new(format.pattern()).expect("format patterns are valid");
+                (*format, regex)
+            })
+            .collect();
+
+        Validator {
+            env,
+            patterns,
+            formats,
+        }
+    }
+
+    /// Validates `payload` of the event at `event`, a component path extended with the event
+    /// name such as `common/PhoneLoginPage/OnLogin`. Returns every violation found, an empty
+    /// vector means the payload is valid.
+    pub fn validate(&self, event: &EPath, payload: &Value) -> Vec<ValidationError> {
+        let Some((_, definition)) = self.env.event(event) else {
+            return vec![ValidationError::UnknownEvent {
+                event: event.clone(),
+            }];
+        };
+        let Value::Object(parameters) = payload else {
+            return vec![ValidationError::NotAnObject {
+                event: event.clone(),
+            }];
+        };
+
+        self.validate_parameters(definition, parameters)
+    }
+
+    pub fn validate_tracked(&self, tracked: &TrackedEvent) -> Vec<ValidationError> {
+        self.validate(&tracked.event, &tracked.properties)
+    }
+
+    fn validate_parameters(
+        &self,
+        event: &Event,
+        parameters: &Map<String, Value>,
+    ) -> Vec<ValidationError> {
+        let mut errors = Vec::new();
+        for argument in event.arguments() {
+            match parameters.get(argument.name()) {
+                Some(Value::Null) if !argument.required() => {}
+                Some(value) => {
+                    self.validate_value(argument.name(), argument.type_ref(), value, &mut errors)
+                }
+                None if !argument.required() => {}
+                None => errors.push(ValidationError::MissingParameter {
+                    parameter: argument.name().to_string(),
+                }),
+            }
+        }
+
+        for name in parameters.keys() {
+            if !event
+                .arguments()
+                .iter()
+                .any(|argument| argument.name() == name)
+            {
+                errors.push(ValidationError::UnknownParameter {
+                    parameter: name.clone(),
+                });
+            }
+        }
+        errors
+    }
+
+    /// Validates the value of the parameter or object field at `parameter`, a dotted path such
+    /// as `item.sku` for fields of objects.
+    pub(crate) fn validate_value(
+        &self,
+        parameter: &str,
+        type_ref: &TypeDefinitionIdentifier,
+        value: &Value,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        if let Some(expected) = self.env.primitive_type(type_ref) {
+            if !matches_primitive(expected, value) {
+                errors.push(ValidationError::TypeMismatch {
+                    parameter: parameter.to_string(),
+                    expected,
+                    actual: json_type(value),
+                });
+                return;
+            }
+        }
+
+        if let Some(collection) = self.env.collection_definition(type_ref) {
+            self.validate_elements(parameter, collection, value, errors);
+            return;
+        }
+
+        if let Some(object_definition) = self.env.object_definition(type_ref) {
+            match value {
+                Value::Object(fields) => {
+                    self.validate_fields(parameter, object_definition, fields, errors)
+                }
+                _ => errors.push(ValidationError::ObjectExpected {
+                    parameter: parameter.to_string(),
+                    actual: json_type(value),
+                }),
+            }
+            return;
+        }
+
+        if let Some(enum_definition) = self.env.enum_definition(type_ref) {
+            let matches = match value {
+                Value::String(value) => enum_definition.contains(value),
+                Value::Number(number) => enum_definition
+                    .values()
+                    .iter()
+                    .any(|candidate| candidate.value().parse::<f64>().ok() == number.as_f64()),
+                _ => false,
+            };
+            if !matches {
+                errors.push(ValidationError::NotInEnum {
+                    parameter: parameter.to_string(),
+                    value: value.to_string(),
+                    values: enum_definition
+                        .values()
+                        .iter()
+                        .map(|value| value.value().to_string())
+                        .collect(),
+                });
+            }
+        }
+
+        for constraint in self.violated_constraints(&self.env.constraints(type_ref), value) {
+            errors.push(ValidationError::ConstraintViolation {
+                parameter: parameter.to_string(),
+                constraint,
+                value: value.to_string(),
+            });
+        }
+
+        let Value::String(value) = value else {
+            return;
+        };
+        for pattern in self.env.patterns(type_ref) {
+            let matches = match self.patterns.get(pattern) {
+                Some(Some(regex)) => regex.is_match(value),
+                _ => {
+                    errors.push(ValidationError::InvalidPattern {
+                        parameter: parameter.to_string(),
+                        pattern: pattern.to_string(),
+                    });
+                    continue;
+                }
+            };
+            if !matches {
+                errors.push(ValidationError::PatternMismatch {
+                    parameter: parameter.to_string(),
+                    pattern: pattern.to_string(),
+                    value: value.clone(),
+                });
+            }
+        }
+    }
+
+    fn validate_fields(
+        &self,
+        parameter: &str,
+        object: &ObjectTypeDefinition,
+        fields: &Map<String, Value>,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        for field in object.fields() {
+            let path = format!("{}.{}", parameter, field.name());
+            match fields.get(field.name()) {
+                Some(Value::Null) if !field.required() => {}
+                Some(value) => self.validate_value(&path, field.type_ref(), value, errors),
+                None if !field.required() => {}
+                None => errors.push(ValidationError::MissingParameter { parameter: path }),
+            }
+        }
+
+        for name in fields.keys() {
+            if object.field(name).is_none() {
+                errors.push(ValidationError::UnknownParameter {
+                    parameter: format!("{}.{}", parameter, name),
+                });
+            }
+        }
+    }
+
+    /// Validates every element of a list at `parameter[i]` and every value of a map at
+    /// `parameter.key`.
+    fn validate_elements(
+        &self,
+        parameter: &str,
+        collection: &CollectionTypeDefinition,
+        value: &Value,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        match (collection.kind(), value) {
+            (CollectionKind::List, Value::Array(elements)) => {
+                for (index, element) in elements.iter().enumerate() {
+                    let path = format!("{}[{}]", parameter, index);
+                    self.validate_value(&path, collection.element(), element, errors);
+                }
+            }
+            (CollectionKind::Map, Value::Object(entries)) => {
+                for (key, element) in entries {
+                    let path = format!("{}.{}", parameter, key);
+                    self.validate_value(&path, collection.element(), element, errors);
+                }
+            }
+            (CollectionKind::List, _) => errors.push(ValidationError::ListExpected {
+                parameter: parameter.to_string(),
+                actual: json_type(value),
+            }),
+            (CollectionKind::Map, _) => errors.push(ValidationError::ObjectExpected {
+                parameter: parameter.to_string(),
+                actual: json_type(value),
+            }),
+        }
+    }
+
+    /// Descriptions of the constraints `value` violates, such as `maximum 150`.
+    pub(crate) fn violated_constraints(
+        &self,
+        constraints: &TypeConstraints,
+        value: &Value,
+    ) -> Vec<String> {
+        let mut buf = Vec::new();
+        match value {
+            Value::String(value) => {
+                let length = value.chars().count() as u64;
+                if let Some(min_length) = constraints.min_length.filter(|min| length < *min) {
+                    buf.push(format!("minLength {}", min_length));
+                }
+                if let Some(max_length) = constraints.max_length.filter(|max| length > *max) {
+                    buf.push(format!("maxLength {}", max_length));
+                }
+                if let Some(format) = constraints.format {
+                    if !self.formats[&format].is_match(value) {
+                        buf.push(format!("format {}", format.name()));
+                    }
+                }
+            }
+            Value::Number(number) => {
+                let number = number.as_f64().unwrap_or_default();
+                if let Some(minimum) = constraints.minimum.filter(|min| number < *min) {
+                    buf.push(format!("minimum {}", minimum));
+                }
+                if let Some(maximum) = constraints.maximum.filter(|max| number > *max) {
+                    buf.push(format!("maximum {}", maximum));
+                }
+                if let Some(multiple_of) = constraints.multiple_of {
+                    if !constraints.is_multiple(number) {
+                        buf.push(format!("multipleOf {}", multiple_of));
+                    }
+                }
+            }
+            _ => {}
+        }
+        buf
+    }
+}
+
+fn matches_primitive(expected: PrimitiveType, value: &Value) -> bool {
+    match expected {
+        PrimitiveType::String => value.is_string(),
+        PrimitiveType::Integer | PrimitiveType::Long => value.is_i64() || value.is_u64(),
+        PrimitiveType::Float => value.is_number(),
+        PrimitiveType::Boolean => value.is_boolean(),
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "float",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ValidationError {
+    #[error("Unknown event {event:?}")]
+    UnknownEvent { event: EPath },
+
+    #[error("Payload of event {event:?} is not a JSON object")]
+    NotAnObject { event: EPath },
+
+    #[error("Missing parameter '{parameter}'")]
+    MissingParameter { parameter: String },
+
+    #[error("Unknown parameter '{parameter}'")]
+    UnknownParameter { parameter: String },
+
+    #[error("Parameter '{parameter}' is expected to be {expected:?}, got {actual}")]
+    TypeMismatch {
+        parameter: String,
+        expected: PrimitiveType,
+        actual: &'static str,
+    },
+
+    #[error("Parameter '{parameter}' is expected to be an object, got {actual}")]
+    ObjectExpected {
+        parameter: String,
+        actual: &'static str,
+    },
+
+    #[error("Parameter '{parameter}' is expected to be a list, got {actual}")]
+    ListExpected {
+        parameter: String,
+        actual: &'static str,
+    },
+
+    #[error("Parameter '{parameter}' value '{value}' does not match pattern '{pattern}'")]
+    PatternMismatch {
+        parameter: String,
+        pattern: String,
+        value: String,
+    },
+
+    #[error("Parameter '{parameter}' value {value} violates {constraint}")]
+    ConstraintViolation {
+        parameter: String,
+        constraint: String,
+        value: String,
+    },
+
+    #[error("Parameter '{parameter}' value {value} is not one of {}", values.join(", "))]
+    NotInEnum {
+        parameter: String,
+        value: String,
+        values: Vec<String>,
+    },
+
+    #[error("Pattern '{pattern}' of parameter '{parameter}' is not a valid regular expression")]
+    InvalidPattern { parameter: String, pattern: String },
+}
+
+impl ValidationError {
+    /// Message without the offending value, so that occurrences of the same violation can be
+    /// counted together.
+    pub fn summary(&self) -> String {
+        match self {
+            ValidationError::PatternMismatch {
+                parameter, pattern, ..
+            } => format!(
+                "Parameter '{}' does not match pattern '{}'",
+                parameter, pattern
+            ),
+            ValidationError::ConstraintViolation {
+                parameter,
+                constraint,
+                ..
+            } => format!("Parameter '{}' violates {}", parameter, constraint),
+            ValidationError::NotInEnum {
+                parameter, values, ..
+            } => format!(
+                "Parameter '{}' is not one of {}",
+                parameter,
+                values.join(", ")
+            ),
+            _ => self.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::{EnumValue, EventArgument, ObjectField, TypeDefinition};
+    use crate::util::{example_environment, std_type, type_identifier};
+    use serde_json::json;
+    use std::str::FromStr;
+
+    fn on_login() -> EPath {
+        EPath::from_str("common/PhoneLoginPage/OnLogin").unwrap()
+    }
+
+    #[test]
+    fn test_valid_payload() {
+        let env = example_environment();
+        let errors = Validator::of(&env).validate(
+            &on_login(),
+            &json!({ "phone": "+1-5417543010", "passcode": "1234" }),
+        );
+
+        assert_eq!(errors, vec![]);
+    }
+
+    #[test]
+    fn test_missing_and_unknown_parameters() {
+        let env = example_environment();
+        let errors = Validator::of(&env).validate(
+            &on_login(),
+            &json!({ "phone": "+1-5417543010", "pin": "1" }),
+        );
+
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::MissingParameter {
+                    parameter: "passcode".to_string()
+                },
+                ValidationError::UnknownParameter {
+                    parameter: "pin".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_type_and_pattern_mismatch() {
+        let env = example_environment();
+        let errors = Validator::of(&env).validate(
+            &on_login(),
+            &json!({ "phone": "not a phone", "passcode": 1234 }),
+        );
+
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::TypeMismatch {
+                    parameter: "passcode".to_string(),
+                    expected: PrimitiveType::String,
+                    actual: "integer",
+                },
+                ValidationError::PatternMismatch {
+                    parameter: "phone".to_string(),
+                    pattern: "^\\+?[0-9]{1,3}-?[0-9]{1,14}$".to_string(),
+                    value: "not a phone".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_constraints() {
+        let mut env = example_environment();
+        env.push_type_definition(TypeDefinition::new_alias(
+            std_type("Age"),
+            std_type("Integer"),
+            None,
+            None,
+            TypeConstraints {
+                minimum: Some(0.0),
+                maximum: Some(150.0),
+                ..Default::default()
+            },
+        ));
+        env.push_type_definition(TypeDefinition::new_alias(
+            std_type("AdultAge"),
+            std_type("Age"),
+            None,
+            None,
+            TypeConstraints {
+                minimum: Some(18.0),
+                multiple_of: Some(2.0),
+                ..Default::default()
+            },
+        ));
+        let validator = Validator::of(&env);
+        let age = std_type("AdultAge");
+        let mut errors = Vec::new();
+        validator.validate_value("age", &age, &json!(200), &mut errors);
+        validator.validate_value("age", &age, &json!(17), &mut errors);
+        validator.validate_value("age", &age, &json!(40), &mut errors);
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(ValidationError::summary)
+                .collect::<Vec<String>>(),
+            vec![
+                "Parameter 'age' violates maximum 150",
+                "Parameter 'age' violates minimum 18",
+                "Parameter 'age' violates multipleOf 2",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enum() {
+        let mut env = example_environment();
+        let identifier = type_identifier("tuna-bank:client-app", "common/LoginMethod");
+        env.push_type_definition(TypeDefinition::new_enum(
+            identifier.clone(),
+            None,
+            vec![
+                EnumValue::new("sms", None),
+                EnumValue::new("password", None),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let validator = Validator::of(&env);
+        let mut errors = Vec::new();
+        validator.validate_value("method", &identifier, &json!("sms"), &mut errors);
+        validator.validate_value("method", &identifier, &json!("pin"), &mut errors);
+
+        assert_eq!(
+            errors,
+            vec![ValidationError::NotInEnum {
+                parameter: "method".to_string(),
+                value: "\"pin\"".to_string(),
+                values: vec!["sms".to_string(), "password".to_string()],
+            }]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Parameter 'method' value \"pin\" is not one of sms, password"
+        );
+    }
+
+    #[test]
+    fn test_object() {
+        let mut env = example_environment();
+        let cart_item = type_identifier("tuna-bank:client-app", "CartItem");
+        env.push_type_definition(TypeDefinition::new_object(
+            cart_item.clone(),
+            vec![
+                ObjectField::new("quantity", std_type("Integer")),
+                ObjectField::new("sku", std_type("String")),
+            ],
+            None,
+            None,
+            TypeConstraints::default(),
+        ));
+        let validator = Validator::of(&env);
+        let mut errors = Vec::new();
+        validator.validate_value(
+            "item",
+            &cart_item,
+            &json!({ "quantity": 2, "sku": "A-1" }),
+            &mut errors,
+        );
+        validator.validate_value(
+            "item",
+            &cart_item,
+            &json!({ "quantity": "2", "price": 1.5 }),
+            &mut errors,
+        );
+        validator.validate_value("item", &cart_item, &json!("A-1"), &mut errors);
+
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::TypeMismatch {
+                    parameter: "item.quantity".to_string(),
+                    expected: PrimitiveType::Integer,
+                    actual: "string",
+                },
+                ValidationError::MissingParameter {
+                    parameter: "item.sku".to_string()
+                },
+                ValidationError::UnknownParameter {
+                    parameter: "item.price".to_string()
+                },
+                ValidationError::ObjectExpected {
+                    parameter: "item".to_string(),
+                    actual: "string",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_optional_parameters() {
+        let env = example_environment();
+        let string = std_type("String");
+        let event = Event::new(
+            "OnSearch",
+            vec![
+                EventArgument::new("query", string.clone()),
+                EventArgument::new_optional("referrer", string.clone(), None),
+                EventArgument::new_optional("source", string, Some(json!("home"))),
+            ],
+        );
+        let validator = Validator::of(&env);
+        let parameters = |value: Value| value.as_object().unwrap().clone();
+
+        assert_eq!(
+            validator.validate_parameters(&event, &parameters(json!({ "query": "tuna" }))),
+            vec![]
+        );
+        assert_eq!(
+            validator.validate_parameters(
+                &event,
+                &parameters(json!({ "query": "tuna", "referrer": null, "source": 1 }))
+            ),
+            vec![ValidationError::TypeMismatch {
+                parameter: "source".to_string(),
+                expected: PrimitiveType::String,
+                actual: "integer",
+            }]
+        );
+        assert_eq!(
+            validator.validate_parameters(&event, &parameters(json!({ "query": null }))),
+            vec![ValidationError::TypeMismatch {
+                parameter: "query".to_string(),
+                expected: PrimitiveType::String,
+                actual: "null",
+            }]
+        );
+    }
+
+    #[test]
+    fn test_collections() {
+        let mut env = example_environment();
+        let integer = std_type("Integer");
+        let list = TypeDefinitionIdentifier::collection(CollectionKind::List, &integer);
+        let map = TypeDefinitionIdentifier::collection(CollectionKind::Map, &list);
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::List,
+            integer,
+        ));
+        env.push_type_definition(TypeDefinition::new_collection(
+            CollectionKind::Map,
+            list.clone(),
+        ));
+        let validator = Validator::of(&env);
+        let mut errors = Vec::new();
+        validator.validate_value(
+            "scores",
+            &map,
+            &json!({ "a": [1, 2], "b": [] }),
+            &mut errors,
+        );
+        validator.validate_value(
+            "scores",
+            &map,
+            &json!({ "a": [1, "2"], "b": 3 }),
+            &mut errors,
+        );
+        validator.validate_value("scores", &map, &json!([]), &mut errors);
+
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::TypeMismatch {
+                    parameter: "scores.a[1]".to_string(),
+                    expected: PrimitiveType::Integer,
+                    actual: "string",
+                },
+                ValidationError::ListExpected {
+                    parameter: "scores.b".to_string(),
+                    actual: "integer",
+                },
+                ValidationError::ObjectExpected {
+                    parameter: "scores".to_string(),
+                    actual: "array",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_format() {
+        let env = example_environment();
+        let validator = Validator::of(&env);
+        let constraints = TypeConstraints {
+            format: Some(StringFormat::Email),
+            max_length: Some(12),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            validator.violated_constraints(&constraints, &json!("qa@tuna.bank")),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            validator.violated_constraints(&constraints, &json!("not an email")),
+            vec!["format email"]
+        );
+    }
+
+    #[test]
+    fn test_stdlib_types() {
+        let env = example_environment();
+        let validator = Validator::of(&env);
+        let mut errors = Vec::new();
+        validator.validate_value("enabled", &std_type("Boolean"), &json!(true), &mut errors);
+        validator.validate_value("enabled", &std_type("Boolean"), &json!("true"), &mut errors);
+        validator.validate_value(
+            "birthday",
+            &std_type("Date"),
+            &json!("2024-02-29"),
+            &mut errors,
+        );
+        validator.validate_value(
+            "birthday",
+            &std_type("Date"),
+            &json!("29.02.2024"),
+            &mut errors,
+        );
+        validator.validate_value(
+            "currency",
+            &std_type("CurrencyCode"),
+            &json!("eur"),
+            &mut errors,
+        );
+
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::TypeMismatch {
+                    parameter: "enabled".to_string(),
+                    expected: PrimitiveType::Boolean,
+                    actual: "string",
+                },
+                ValidationError::ConstraintViolation {
+                    parameter: "birthday".to_string(),
+                    constraint: "format date".to_string(),
+                    value: "\"29.02.2024\"".to_string(),
+                },
+                ValidationError::PatternMismatch {
+                    parameter: "currency".to_string(),
+                    pattern: "^[A-Z]{3}$".to_string(),
+                    value: "eur".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_event() {
+        let env = example_environment();
+        let validator = Validator::of(&env);
+        let event = EPath::from_str("common/PhoneLoginPage/OnLogout").unwrap();
+
+        assert_eq!(
+            validator.validate(&event, &json!({})),
+            vec![ValidationError::UnknownEvent { event }]
+        );
+        assert_eq!(
+            validator.validate(&on_login(), &json!([])),
+            vec![ValidationError::NotAnObject { event: on_login() }]
+        );
+    }
+}
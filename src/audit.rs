@@ -0,0 +1,74 @@
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use open_ams_engine::entity::Environment;
+use open_ams_engine::validator::{AuditReport, TrackedEvent, ValidationSummary, Validator};
+
+/// Validates every line of an NDJSON event log against the spec.
+pub fn audit(env: &Environment, log: &Path) -> Result<AuditReport, Box<dyn Error>> {
+    let validator = Validator::of(env);
+    let mut summary = ValidationSummary::default();
+
+    for line in BufReader::new(File::open(log)?).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str::<TrackedEvent>(&line) {
+            Ok(tracked) => summary.record(&tracked.event, &validator.validate_tracked(&tracked)),
+            Err(_) => summary.record_malformed(),
+        }
+    }
+    Ok(summary.report(env))
+}
+
+pub fn write_report(report: &AuditReport, path: &Path) -> Result<(), Box<dyn Error>> {
+    fs::write(path, serde_json::to_string_pretty(report)?)?;
+    Ok(())
+}
+
+pub fn print_summary(report: &AuditReport) {
+    println!(
+        "{} events: {} passed, {} failed, {} malformed",
+        report.total, report.passed, report.failed, report.malformed
+    );
+
+    println!("\nEvents with errors:");
+    let mut clean = true;
+    for (event, stats) in &report.events {
+        if stats.failed == 0 {
+            continue;
+        }
+        clean = false;
+        println!(
+            "    {} ({} of {} failed)",
+            event.to_string(),
+            stats.failed,
+            stats.passed + stats.failed
+        );
+        for (error, count) in &stats.errors {
+            println!("        {} x {}", count, error);
+        }
+    }
+    if clean {
+        println!("    none");
+    }
+
+    println!("\nUnknown events:");
+    if report.unknown_events.is_empty() {
+        println!("    none");
+    }
+    for (event, count) in &report.unknown_events {
+        println!("    {} ({} occurrences)", event.to_string(), count);
+    }
+
+    println!("\nDead events:");
+    if report.dead_events.is_empty() {
+        println!("    none");
+    }
+    for event in &report.dead_events {
+        println!("    {}", event.to_string());
+    }
+}
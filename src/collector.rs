@@ -0,0 +1,185 @@
+use std::error::Error;
+use std::io::Read;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use open_ams_engine::entity::Environment;
+use open_ams_engine::validator::{TrackedEvent, ValidationSummary, Validator};
+use serde_json::json;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Local HTTP endpoint validating every posted event against the spec. Logs a pass/fail line per
+/// event and prints which spec events were seen or never fired once interrupted with Ctrl-C.
+pub struct Collector<'env> {
+    env: &'env Environment,
+    validator: Validator<'env>,
+    summary: ValidationSummary,
+}
+
+impl<'env> Collector<'env> {
+    pub fn of(env: &'env Environment) -> Self {
+        Collector {
+            env,
+            validator: Validator::of(env),
+            summary: ValidationSummary::default(),
+        }
+    }
+
+    pub fn serve(mut self, address: &str) -> Result<(), Box<dyn Error>> {
+        let server = Server::http(address).map_err(|e| e.to_string())?;
+        let running = Arc::new(AtomicBool::new(true));
+        let handler_running = running.clone();
+        ctrlc::set_handler(move || handler_running.store(false, Ordering::SeqCst))?;
+
+        println!(
+            "Collecting events on http://{}, press Ctrl-C to stop",
+            address
+        );
+        while running.load(Ordering::SeqCst) {
+            if let Some(request) = server.recv_timeout(Duration::from_millis(200))? {
+                self.handle(request);
+            }
+        }
+
+        self.print_summary();
+        Ok(())
+    }
+
+    fn handle(&mut self, mut request: Request) {
+        if *request.method() != Method::Post {
+            respond(
+                request,
+                Response::from_string("Only POST is supported").with_status_code(405),
+            );
+            return;
+        }
+
+        let mut body = String::new();
+        if let Err(error) = request.as_reader().read_to_string(&mut body) {
+            respond(
+                request,
+                Response::from_string(error.to_string()).with_status_code(400),
+            );
+            return;
+        }
+
+        let (passed, failed) = self.validate_batch(&body);
+        let content_type =
+            Header::from_bytes("Content-Type", "application/json").expect("static header is valid");
+        respond(
+            request,
+            Response::from_string(json!({ "passed": passed, "failed": failed }).to_string())
+                .with_header(content_type),
+        );
+    }
+
+    fn validate_batch(&mut self, body: &str) -> (usize, usize) {
+        let (mut passed, mut failed) = (0, 0);
+        for tracked in TrackedEvent::parse_batch(body) {
+            let tracked = match tracked {
+                Ok(tracked) => tracked,
+                Err(error) => {
+                    println!("FAIL <malformed event>");
+                    println!("    - {}", error);
+                    self.summary.record_malformed();
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            let errors = self.validator.validate_tracked(&tracked);
+            self.summary.record(&tracked.event, &errors);
+            if errors.is_empty() {
+                println!("PASS {}", tracked.event.to_string());
+                passed += 1;
+            } else {
+                println!("FAIL {}", tracked.event.to_string());
+                for error in errors {
+                    println!("    - {}", error);
+                }
+                failed += 1;
+            }
+        }
+        (passed, failed)
+    }
+
+    fn print_summary(&self) {
+        println!("\nSeen events:");
+        if self.summary.seen().is_empty() {
+            println!("    none");
+        }
+        for (event, stats) in self.summary.seen() {
+            println!(
+                "    {} ({} passed, {} failed)",
+                event.to_string(),
+                stats.passed,
+                stats.failed
+            );
+        }
+
+        println!("Never fired:");
+        let never_fired = self.summary.never_fired(self.env);
+        if never_fired.is_empty() {
+            println!("    none");
+        }
+        for event in never_fired {
+            println!("    {}", event.to_string());
+        }
+    }
+}
+
+/// Sends `response`, logging rather than returning failures: a client going away before reading
+/// its response must not stop the collector.
+fn respond<R: Read>(request: Request, response: Response<R>) {
+    let remote_addr = request.remote_addr().copied();
+    if let Err(error) = request.respond(response) {
+        match remote_addr {
+            Some(address) => eprintln!("error: failed to respond to {}: {}", address, error),
+            None => eprintln!("error: failed to respond: {}", error),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use open_ams_engine::entity::ProjectIdentifier;
+    use open_ams_engine::source::entity::Version;
+    use std::io;
+    use std::str::FromStr;
+    use tiny_http::{StatusCode, TestRequest};
+
+    /// Response body failing halfway, as when the client disconnects.
+    struct BrokenBody;
+
+    impl Read for BrokenBody {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::TimedOut, "client went away"))
+        }
+    }
+
+    #[test]
+    fn test_keeps_serving_after_failed_response() {
+        let env = Environment::empty(
+            ProjectIdentifier::new("tuna-bank", "client-app"),
+            Version::from_str("1.0.0").unwrap(),
+        );
+        let mut collector = Collector::of(&env);
+
+        respond(
+            TestRequest::new().into(),
+            Response::new(StatusCode(200), vec![], BrokenBody, Some(16), None),
+        );
+        for _ in 0..2 {
+            collector.handle(
+                TestRequest::new()
+                    .with_method(Method::Post)
+                    .with_body("not json")
+                    .into(),
+            );
+        }
+
+        assert_eq!(collector.summary.malformed(), 2);
+    }
+}
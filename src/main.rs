@@ -1,3 +1,213 @@
-fn main() {
-    println!("Hello, world!");
+mod audit;
+mod collector;
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+use open_ams_engine::codegen::CodeGenerator;
+use open_ams_engine::entity::Environment;
+use open_ams_engine::export::{
+    DiagramExporter, DiagramFormat, ProtobufExporter, ProtobufFieldNumbers,
+};
+use open_ams_engine::generator::PayloadGenerator;
+use open_ams_engine::resolver::{deprecation_warnings, Resolver};
+use open_ams_engine::source::entity::{EPath, SourceEnvironment, TargetLanguage};
+
+use crate::collector::Collector;
+
+/// File next to the exported `.proto` files keeping the field numbers stable across exports.
+const FIELD_NUMBERS_FILE: &str = "field_numbers.yaml";
+
+#[derive(Debug, Parser)]
+#[command(name = "open-ams", version, about = "Analytics tracking plan toolkit")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Render the component tree of a project as a Mermaid or DOT diagram
+    Diagram {
+        /// Path to the project directory containing `ams.yaml`
+        project: PathBuf,
+
+        /// Render only the tree below this component, e.g. `common/PhoneLoginPage`
+        #[arg(long)]
+        component: Option<EPath>,
+
+        /// Output format: `mermaid` or `dot`
+        #[arg(long, default_value = "mermaid")]
+        format: DiagramFormat,
+    },
+
+    /// Generate native declarations, such as enums, of the project types
+    Codegen {
+        /// Path to the project directory containing `ams.yaml`
+        project: PathBuf,
+
+        /// Target language: `kotlin`, `java`, `swift` or `dart`
+        #[arg(long)]
+        language: TargetLanguage,
+
+        /// Directory to write the generated sources to
+        #[arg(long, default_value = "generated")]
+        output: PathBuf,
+    },
+
+    /// Export the events as Protocol Buffers messages, one `.proto` file per module
+    Protobuf {
+        /// Path to the project directory containing `ams.yaml`
+        project: PathBuf,
+
+        /// Directory to write the `.proto` files to, field numbers assigned so far are kept in
+        /// `field_numbers.yaml` in the same directory
+        #[arg(long, default_value = "proto")]
+        output: PathBuf,
+    },
+
+    /// Start a local HTTP endpoint validating posted JSON or NDJSON events against the spec
+    Collect {
+        /// Path to the project directory containing `ams.yaml`
+        project: PathBuf,
+
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8787")]
+        address: String,
+    },
+
+    /// Validate an NDJSON event log against the spec and report errors, unknown and dead events
+    Audit {
+        /// Path to the project directory containing `ams.yaml`
+        project: PathBuf,
+
+        /// NDJSON file with one `{"event": ..., "properties": ...}` object per line
+        log: PathBuf,
+
+        /// Write the machine-readable JSON report to this file
+        #[arg(long)]
+        report: Option<PathBuf>,
+    },
+
+    /// Generate synthetic events as NDJSON
+    Generate {
+        /// Path to the project directory containing `ams.yaml`
+        project: PathBuf,
+
+        /// Generate only this event, e.g. `common/PhoneLoginPage/OnLogin`
+        #[arg(long)]
+        event: Option<EPath>,
+
+        /// Number of events to generate
+        #[arg(long, default_value_t = 100)]
+        count: usize,
+
+        /// Seed of the random generator, the same seed yields the same events
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    match cli.command {
+        Command::Diagram {
+            project,
+            component,
+            format,
+        } => {
+            let env = load_environment(&project)?;
+            let exporter = DiagramExporter::of(&env, format);
+            let diagram = match component {
+                Some(epath) => exporter.render_component(&epath)?,
+                None => exporter.render_project(),
+            };
+            print!("{}", diagram);
+        }
+        Command::Codegen {
+            project,
+            language,
+            output,
+        } => {
+            let env = load_environment(&project)?;
+            for file in CodeGenerator::of(&env, language).export()? {
+                file.write_to(&output)?;
+                println!("{}", output.join(file.path()).display());
+            }
+        }
+        Command::Protobuf { project, output } => {
+            let env = load_environment(&project)?;
+            let numbers_path = output.join(FIELD_NUMBERS_FILE);
+            let mut field_numbers = ProtobufFieldNumbers::load(&numbers_path)?;
+            for file in ProtobufExporter::of(&env).export(&mut field_numbers)? {
+                file.write_to(&output)?;
+                println!("{}", output.join(file.path()).display());
+            }
+            fs::create_dir_all(&output)?;
+            field_numbers.save(&numbers_path)?;
+        }
+        Command::Collect { project, address } => {
+            let env = load_environment(&project)?;
+            Collector::of(&env).serve(&address)?;
+        }
+        Command::Audit {
+            project,
+            log,
+            report,
+        } => {
+            let env = load_environment(&project)?;
+            let audit_report = audit::audit(&env, &log)?;
+            if let Some(path) = report {
+                audit::write_report(&audit_report, &path)?;
+            }
+            audit::print_summary(&audit_report);
+        }
+        Command::Generate {
+            project,
+            event,
+            count,
+            seed,
+        } => {
+            let env = load_environment(&project)?;
+            let events = PayloadGenerator::of(&env, seed).generate(count, event.as_ref())?;
+            let mut out = BufWriter::new(io::stdout().lock());
+            for event in events {
+                writeln!(out, "{}", event)?;
+            }
+            out.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Loads the project at `path` next to the standard library and resolves it, reporting
+/// deprecated declarations of other projects it still uses.
+fn load_environment(path: &Path) -> Result<Environment, Box<dyn Error>> {
+    let mut source = SourceEnvironment::default()?;
+    source.load_local(path)?;
+    let target = source
+        .projects()
+        .last()
+        .map(|project| project.identifier())
+        .ok_or("No project loaded")?;
+    source.set_target_project(target);
+
+    let environment = Resolver::of(&source).resolve()?;
+    for warning in deprecation_warnings(&environment) {
+        eprintln!("warning: {}", warning);
+    }
+    Ok(environment)
 }